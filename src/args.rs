@@ -6,6 +6,11 @@ pub fn arg<T: ToArg>(arg: T) -> Arg {
     arg.to_arg()
 }
 
+impl ToArg for Arg {
+    fn to_arg(self) -> Arg {
+        self
+    }
+}
 impl ToArg for usize {
     fn to_arg(self) -> Arg {
         Arg::Uint(self)
@@ -56,33 +61,13 @@ impl ToArg for f64 {
         Arg::Float(self)
     }
 }
-impl ToArg for Vec<usize> {
-    fn to_arg(self) -> Arg {
-        Arg::Set(self.into_iter().map(|x| x.to_arg()).collect())
-    }
-}
-impl ToArg for Vec<isize> {
-    fn to_arg(self) -> Arg {
-        Arg::Set(self.into_iter().map(|x| x.to_arg()).collect())
-    }
-}
-impl ToArg for Vec<bool> {
-    fn to_arg(self) -> Arg {
-        Arg::Set(self.into_iter().map(|x| x.to_arg()).collect())
-    }
-}
-impl ToArg for Vec<String> {
-    fn to_arg(self) -> Arg {
-        Arg::Set(self.into_iter().map(|x| x.to_arg()).collect())
-    }
-}
-impl ToArg for Vec<f64> {
+impl<T: ToArg> ToArg for Vec<T> {
     fn to_arg(self) -> Arg {
         Arg::Set(self.into_iter().map(|x| x.to_arg()).collect())
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Arg {
     Uint(usize),
     Int(isize),
@@ -92,16 +77,3 @@ pub enum Arg {
     Set(Vec<Arg>),
     Null,
 }
-
-impl Clone for Arg {
-    fn clone(&self) -> Self {
-        match self {
-            Arg::Uint(v) => Arg::Uint(*v),
-            Arg::Int(v) => Arg::Int(*v),
-            Arg::Bool(v) => Arg::Bool(*v),
-            Arg::Str(v) => Arg::Str(v.clone()),
-            Arg::Float(v) => Arg::Float(*v),
-            _ => Arg::Null,
-        }
-    }
-}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dialect {
+    MySQL,
+    MariaDB,
+    Postgres,
+}
+
+impl fmt::Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dialect::MySQL => write!(f, "MySQL"),
+            Dialect::MariaDB => write!(f, "MariaDB"),
+            Dialect::Postgres => write!(f, "Postgres"),
+        }
+    }
+}
+
+impl Dialect {
+    /// Builders render MySQL syntax; this rewrites a finished statement into the dialect's own.
+    /// For Postgres that means `"ident"` quoting, `$1, $2, ...` placeholders and standard string literals.
+    pub fn finish_sql(&self, sql: String) -> String {
+        match self {
+            Dialect::MySQL | Dialect::MariaDB => sql,
+            Dialect::Postgres => to_postgres(&sql),
+        }
+    }
+}
+
+fn to_postgres(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut placeholder = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => {
+                let mut ident = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '`' if chars.peek() == Some(&'`') => {
+                            chars.next();
+                            ident.push('`');
+                        }
+                        '`' => break,
+                        c => ident.push(c),
+                    }
+                }
+                out.push_str(format!("\"{}\"", ident.replace('"', "\"\"")).as_str());
+            }
+            '\'' => {
+                // MySQL escapes with backslashes; Postgres literals take them verbatim
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => text.extend(chars.next()),
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            text.push('\'');
+                        }
+                        '\'' => break,
+                        c => text.push(c),
+                    }
+                }
+                out.push_str(format!("'{}'", text.replace('\'', "''")).as_str());
+            }
+            '?' => {
+                placeholder += 1;
+                out.push_str(format!("${placeholder}").as_str());
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,24 @@
+use crate::dialect::*;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum BuildError {
+    Unsupported {
+        feature: &'static str,
+        dialect: Dialect,
+    },
+    Invalid(&'static str),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::Unsupported { feature, dialect } => {
+                write!(f, "{dialect} does not support {feature}")
+            }
+            BuildError::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
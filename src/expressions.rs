@@ -1,20 +1,30 @@
 use crate::args::*;
+use crate::functions::{cast, SqlType};
 use crate::mysql::*;
 use crate::table::*;
 use crate::traits::*;
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct Set(Vec<Exp>);
 impl Set {
     pub fn new(val: Vec<Exp>) -> Self {
         Set(val)
     }
-}
-impl ToSQL for Set {
-    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+    /// The assignments with bare column names on the left, for Postgres, which rejects qualified targets.
+    pub fn to_unqualified_sql(&self) -> (String, Option<Vec<Arg>>) {
+        self.render(|e| match e {
+            Exp::Exp(e) => e.to_assign_sql(),
+            e => e.to_sql(),
+        })
+    }
+    fn render(
+        &self,
+        each: impl Fn(&Exp) -> (String, Option<Vec<Arg>>),
+    ) -> (String, Option<Vec<Arg>>) {
         let mut sql_statements = vec![];
         let mut args = vec![];
         self.0.iter().for_each(|e| {
-            let (sql, op) = e.to_sql();
+            let (sql, op) = each(e);
             sql_statements.push(sql);
             if let Some(a) = op {
                 args.extend(a);
@@ -24,48 +34,108 @@ impl ToSQL for Set {
     }
 }
 
+impl ToSQL for Set {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        self.render(|e| e.to_sql())
+    }
+}
+
+impl Subqueries for Set {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.0.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Insert {
     keys: Vec<String>,
-    values: Vec<Vec<Arg>>,
+    values: Vec<Vec<ExpTar>>,
 }
 impl Insert {
-    pub fn new(keys: Vec<String>, values: Vec<Vec<Arg>>) -> Self {
-        Insert {
-            keys: keys,
-            values: values,
-        }
+    pub fn new(keys: Vec<String>, values: Vec<Vec<ExpTar>>) -> Self {
+        Insert { keys, values }
     }
 }
 impl ToSQL for Insert {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
-        let key_query = format!(
-            "({})",
-            self.keys
-                .iter()
-                .map(|k| format!("`{k}`"))
-                .collect::<Vec<String>>()
-                .join(", ")
-        );
-        let values_query = (0..self.values.len())
-            .map(|_| {
-                format!(
-                    "\t({})",
-                    (0..self.keys.len())
-                        .map(|_| "?")
-                        .collect::<Vec<&str>>()
-                        .join(", ")
-                )
-            })
+        let mut args = Vec::new();
+        let values_query = self
+            .values
+            .iter()
+            .map(|row| format!("\t({})", row_sql(row, &mut args)))
             .collect::<Vec<String>>()
             .join(",\n");
+        (
+            format!("{} \nVALUES\n {values_query}", keys_sql(&self.keys)),
+            Some(args),
+        )
+    }
+}
+
+impl Subqueries for Insert {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.values.subqueries(f);
+    }
+}
+
+/// A `VALUES ROW(...), ...` table value constructor, used as a table source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Values {
+    keys: Vec<String>,
+    values: Vec<Vec<ExpTar>>,
+}
+impl Values {
+    pub fn new(keys: Vec<String>, values: Vec<Vec<ExpTar>>) -> Self {
+        Values { keys, values }
+    }
+    /// The derived column list, e.g. `` (`id`, `name`) ``.
+    pub fn keys_sql(&self) -> String {
+        keys_sql(&self.keys)
+    }
+}
+impl ToSQL for Values {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
         let mut args = Vec::new();
-        self.values
+        let rows = self
+            .values
             .iter()
-            .for_each(|set| set.iter().for_each(|val| args.push(val.clone())));
-        (format!("{key_query} \nVALUES\n {values_query}"), Some(args))
+            .map(|row| format!("ROW({})", row_sql(row, &mut args)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        (format!("(VALUES {rows})"), Some(args))
     }
 }
 
+impl Subqueries for Values {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.values.subqueries(f);
+    }
+}
+
+fn keys_sql(keys: &[String]) -> String {
+    format!(
+        "({})",
+        keys.iter()
+            .map(|k| format!("`{k}`"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+fn row_sql(row: &[ExpTar], args: &mut Vec<Arg>) -> String {
+    row.iter()
+        .map(|val| {
+            let (cell_sql, cell_args) = val.to_sql();
+            if let Some(a) = cell_args {
+                args.extend(a);
+            }
+            cell_sql
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Op {
     Eq,
     Neq,
@@ -74,7 +144,9 @@ pub enum Op {
     In,
     Is,
     Like,
+    LikeEscaped,
     IsNot,
+    MemberOf,
 }
 
 impl ToSQL for Op {
@@ -88,15 +160,21 @@ impl ToSQL for Op {
             Op::Is => (String::from("IS"), None),
             Op::IsNot => (String::from("IS NOT"), None),
             Op::Like => (String::from("LIKE"), None),
+            Op::LikeEscaped => (String::from("LIKE"), None),
+            Op::MemberOf => (String::from("MEMBER OF"), None),
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
 pub enum Exp {
     Exp(ExpU),
     And(And),
     Set(Vec<Exp>),
     Or(Or),
+    Pred(Col),
+    Exists(Box<MYSQLBuilder>),
 }
 
 impl ToSQL for Exp {
@@ -105,6 +183,11 @@ impl ToSQL for Exp {
             Exp::Exp(e) => e.to_sql(),
             Exp::And(a) => a.to_sql(),
             Exp::Or(o) => o.to_sql(),
+            Exp::Pred(col) => col.to_sql(),
+            Exp::Exists(query) => {
+                let (query_sql, query_args) = query.nested_sql();
+                (format!("EXISTS ({query_sql})"), Some(query_args))
+            }
             Exp::Set(v) => {
                 let mut sql = vec![];
                 let mut args = vec![];
@@ -115,13 +198,39 @@ impl ToSQL for Exp {
                         args.extend(v);
                     }
                 });
-                (format!("{}", sql.join(" AND ")), Some(args))
+                (sql.join(" AND "), Some(args))
             }
         }
     }
 }
 
+impl Subqueries for Exp {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match self {
+            Exp::Exp(e) => e.subqueries(f),
+            Exp::And(a) => a.subqueries(f),
+            Exp::Set(v) => v.subqueries(f),
+            Exp::Or(o) => o.subqueries(f),
+            Exp::Pred(col) => col.subqueries(f),
+            Exp::Exists(query) => f(query),
+        }
+    }
+}
+
+/// `EXISTS (SELECT ...)`.
+pub fn exists(query: MYSQLBuilder) -> Exp {
+    Exp::Exists(Box::new(query))
+}
+
 impl Exp {
+    /// `CAST((exp) AS type)`, e.g. to turn a comparison into a number.
+    pub fn cast(self, to: SqlType) -> Col {
+        cast(self, to)
+    }
+    /// Uses a boolean-valued column expression, e.g. a function call, as a predicate.
+    pub fn pred(col: Col) -> Self {
+        Exp::Pred(col.without_alias())
+    }
     pub fn exp_and(left: Exp, right: Exp) -> Self {
         Exp::And(And {
             left: Box::new(left),
@@ -148,6 +257,7 @@ impl Exp {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct And {
     left: Box<Exp>,
     right: Box<Exp>,
@@ -168,6 +278,14 @@ impl ToSQL for And {
     }
 }
 
+impl Subqueries for And {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.left.subqueries(f);
+        self.right.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Or {
     left: Box<Exp>,
     right: Box<Exp>,
@@ -188,11 +306,85 @@ impl ToSQL for Or {
     }
 }
 
+impl Subqueries for Or {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.left.subqueries(f);
+        self.right.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum ExpTar {
     A(Arg),
     C(Col),
     Null,
-    T(MYSQLBuilder),
+    Default,
+    T(Box<MYSQLBuilder>),
+    Tuple(Vec<ExpTar>),
+}
+
+/// Renders a set of args as `(?, ?)`, with nested sets as row values: `((?, ?), (?, ?))`.
+fn set_sql(set: &[Arg]) -> (String, Vec<Arg>) {
+    let mut sql = vec![];
+    let mut args = vec![];
+    set.iter().for_each(|arg| match arg {
+        Arg::Set(inner) => {
+            let (inner_sql, inner_args) = set_sql(inner);
+            sql.push(inner_sql);
+            args.extend(inner_args);
+        }
+        arg => {
+            sql.push(String::from("?"));
+            args.push(arg.clone());
+        }
+    });
+    (format!("({})", sql.join(", ")), args)
+}
+
+/// A row value such as `(a, b)`, for comparing composite keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tuple(Vec<ExpTar>);
+
+pub fn tuple<T: ToExpTar>(vals: Vec<T>) -> Tuple {
+    Tuple(vals.into_iter().map(|v| v.to_exp_tar()).collect())
+}
+
+impl Tuple {
+    pub fn eq<T: ToExpTar>(self, exp: T) -> Exp {
+        self.make_exp(exp.to_exp_tar(), Op::Eq)
+    }
+    pub fn neq<T: ToExpTar>(self, exp: T) -> Exp {
+        self.make_exp(exp.to_exp_tar(), Op::Neq)
+    }
+    pub fn lt<T: ToExpTar>(self, exp: T) -> Exp {
+        self.make_exp(exp.to_exp_tar(), Op::Lt)
+    }
+    pub fn gt<T: ToExpTar>(self, exp: T) -> Exp {
+        self.make_exp(exp.to_exp_tar(), Op::Gt)
+    }
+    pub fn r#in<T: ToExpTar>(self, exp: T) -> Exp {
+        self.make_exp(exp.to_exp_tar(), Op::In)
+    }
+    fn make_exp(self, comp: ExpTar, op: Op) -> Exp {
+        Exp::Exp(ExpU::new(op, ExpTar::Tuple(self.0), comp))
+    }
+}
+
+impl ToExpTar for Tuple {
+    fn to_exp_tar(self) -> ExpTar {
+        ExpTar::Tuple(self.0)
+    }
+}
+
+impl ToExpTar for Vec<Tuple> {
+    fn to_exp_tar(self) -> ExpTar {
+        ExpTar::Tuple(self.into_iter().map(|t| t.to_exp_tar()).collect())
+    }
+}
+
+/// The `DEFAULT` keyword, for an insert cell that should take the column default.
+pub fn default_value() -> ExpTar {
+    ExpTar::Default
 }
 
 pub trait ToExpTar {
@@ -205,7 +397,7 @@ impl<T: ToArg> From<T> for ExpTar {
 }
 impl From<Col> for ExpTar {
     fn from(col: Col) -> Self {
-        ExpTar::C(col)
+        ExpTar::C(col.without_alias())
     }
 }
 impl<T: ToArg> ToExpTar for T {
@@ -213,9 +405,20 @@ impl<T: ToArg> ToExpTar for T {
         ExpTar::A(self.to_arg())
     }
 }
+impl ToExpTar for MYSQLBuilder {
+    fn to_exp_tar(self) -> ExpTar {
+        ExpTar::T(Box::new(self))
+    }
+}
+
 impl ToExpTar for Col {
     fn to_exp_tar(self) -> ExpTar {
-        ExpTar::C(self)
+        ExpTar::C(self.without_alias())
+    }
+}
+impl ToExpTar for ExpTar {
+    fn to_exp_tar(self) -> ExpTar {
+        self
     }
 }
 
@@ -223,26 +426,43 @@ impl ToSQL for ExpTar {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
         match self {
             ExpTar::Null => (String::from("NULL"), None),
+            ExpTar::Default => (String::from("DEFAULT"), None),
             ExpTar::A(Arg::Set(arg_set)) => {
-                let arg_string: Vec<String> = (0..arg_set.len())
-                    .into_iter()
-                    .map(|_| String::from("?"))
-                    .collect();
-                (
-                    format!("({})", arg_string.join(", ")),
-                    Some(arg_set.iter().map(|arg| arg.clone()).collect()),
-                )
+                let (sql, args) = set_sql(arg_set);
+                (sql, Some(args))
+            }
+            ExpTar::Tuple(vals) => {
+                let mut sql = vec![];
+                let mut args = vec![];
+                vals.iter().for_each(|v| {
+                    let (v_sql, v_args) = v.to_sql();
+                    sql.push(v_sql);
+                    args.extend(v_args.unwrap_or_default());
+                });
+                (format!("({})", sql.join(", ")), Some(args))
             }
             ExpTar::A(arg) => (String::from("?"), Some(vec![arg.clone()])),
-            ExpTar::C(col) => (col.to_sql().0, None),
+            ExpTar::C(col) => col.to_sql(),
             ExpTar::T(sub_query_builder) => {
-                let (sub_query, sub_args) = sub_query_builder.to_sql();
-                (format!("{sub_query}"), Some(sub_args))
+                let (sub_query, sub_args) = sub_query_builder.nested_sql();
+                (format!("({sub_query})"), Some(sub_args))
             }
         }
     }
 }
 
+impl Subqueries for ExpTar {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match self {
+            ExpTar::C(col) => col.subqueries(f),
+            ExpTar::T(query) => f(query),
+            ExpTar::Tuple(vals) => vals.subqueries(f),
+            ExpTar::A(_) | ExpTar::Null | ExpTar::Default => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct ExpU {
     op: Op,
     left: ExpTar,
@@ -253,20 +473,48 @@ impl ExpU {
     pub fn new(op: Op, left: ExpTar, right: ExpTar) -> Self {
         ExpU { op, left, right }
     }
-}
-
-impl ToSQL for ExpU {
-    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+    /// Renders a column on the left by its bare name, as a Postgres `SET` target has to be.
+    fn to_assign_sql(&self) -> (String, Option<Vec<Arg>>) {
+        match &self.left {
+            ExpTar::C(col) => self.render(col.ref_sql()),
+            _ => self.to_sql(),
+        }
+    }
+    fn render(&self, (left, arg): (String, Option<Vec<Arg>>)) -> (String, Option<Vec<Arg>>) {
+        // `IN ()` is a syntax error; an empty list matches no row
+        if let (Op::In, ExpTar::A(Arg::Set(set))) = (&self.op, &self.right) {
+            if set.is_empty() {
+                return (String::from("1 = 0"), None);
+            }
+        }
         let mut args = vec![];
-        let (left, arg) = self.left.to_sql();
         if let Some(v) = arg {
-            args.push(v[0].clone())
+            args.extend(v)
         }
         let (right, arg) = self.right.to_sql();
         if let Some(v) = arg {
-            args.push(v[0].clone())
+            args.extend(v)
         }
         let (op_sql, _) = self.op.to_sql();
+        if let Op::MemberOf = self.op {
+            return (format!("{left} {op_sql}({right})"), Some(args));
+        }
+        if let Op::LikeEscaped = self.op {
+            return (format!("{left} {op_sql} {right} ESCAPE '\\\\'"), Some(args));
+        }
         (format!("{left} {op_sql} {right}"), Some(args))
     }
 }
+
+impl ToSQL for ExpU {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        self.render(self.left.to_sql())
+    }
+}
+
+impl Subqueries for ExpU {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.left.subqueries(f);
+        self.right.subqueries(f);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::args::*;
+use crate::expressions::*;
+use crate::mysql::MYSQLBuilder;
+use crate::table::*;
+use crate::traits::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Unit {
+    Microsecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl ToSQL for Unit {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let unit = match self {
+            Unit::Microsecond => "MICROSECOND",
+            Unit::Second => "SECOND",
+            Unit::Minute => "MINUTE",
+            Unit::Hour => "HOUR",
+            Unit::Day => "DAY",
+            Unit::Week => "WEEK",
+            Unit::Month => "MONTH",
+            Unit::Quarter => "QUARTER",
+            Unit::Year => "YEAR",
+        };
+        (String::from(unit), None)
+    }
+}
+
+pub fn now() -> Col {
+    func("NOW", vec![])
+}
+
+pub fn date_format<T: Into<FuncArg>>(date: T, format: &'static str) -> Col {
+    func("DATE_FORMAT", vec![date.into(), format.into()])
+}
+
+pub fn date_add<T: Into<FuncArg>, A: Into<FuncArg>>(date: T, amount: A, unit: Unit) -> Col {
+    func(
+        "DATE_ADD",
+        vec![date.into(), FuncArg::Interval(Box::new(amount.into()), unit)],
+    )
+}
+
+pub fn date_sub<T: Into<FuncArg>, A: Into<FuncArg>>(date: T, amount: A, unit: Unit) -> Col {
+    func(
+        "DATE_SUB",
+        vec![date.into(), FuncArg::Interval(Box::new(amount.into()), unit)],
+    )
+}
+
+pub fn lower<T: Into<FuncArg>>(val: T) -> Col {
+    func("LOWER", vec![val.into()])
+}
+
+pub fn upper<T: Into<FuncArg>>(val: T) -> Col {
+    func("UPPER", vec![val.into()])
+}
+
+pub fn trim<T: Into<FuncArg>>(val: T) -> Col {
+    func("TRIM", vec![val.into()])
+}
+
+pub fn length<T: Into<FuncArg>>(val: T) -> Col {
+    func("LENGTH", vec![val.into()])
+}
+
+/// `SUBSTRING(val, pos)`, or `SUBSTRING(val, pos, len)` when a length is given.
+pub fn substring<T: Into<FuncArg>>(val: T, pos: i64, len: Option<i64>) -> Col {
+    let mut args = vec![val.into(), pos.into()];
+    if let Some(len) = len {
+        args.push(len.into());
+    }
+    func("SUBSTRING", args)
+}
+
+pub fn round<T: Into<FuncArg>>(val: T, decimals: i32) -> Col {
+    func("ROUND", vec![val.into(), decimals.into()])
+}
+
+pub fn ifnull<T: Into<FuncArg>, F: Into<FuncArg>>(val: T, fallback: F) -> Col {
+    func("IFNULL", vec![val.into(), fallback.into()])
+}
+
+pub fn r#if<T: Into<FuncArg>, F: Into<FuncArg>>(cond: Exp, then: T, otherwise: F) -> Col {
+    func("IF", vec![cond.into(), then.into(), otherwise.into()])
+}
+
+pub fn concat(vals: Vec<FuncArg>) -> Col {
+    func("CONCAT", vals)
+}
+
+pub fn count_all() -> Col {
+    func("COUNT", vec![FuncArg::Star])
+}
+
+pub fn count_distinct(cols: Vec<Col>) -> Col {
+    Func::new("COUNT", cols.into_iter().map(|c| c.into()).collect())
+        .distinct()
+        .into()
+}
+
+pub fn json_objectagg<K: Into<FuncArg>, V: Into<FuncArg>>(key: K, value: V) -> Col {
+    func("JSON_OBJECTAGG", vec![key.into(), value.into()])
+}
+
+/// `GROUPING(cols...)`, which is 1 on the subtotal rows added by `WITH ROLLUP`.
+pub fn grouping(cols: Vec<Col>) -> Col {
+    func("GROUPING", cols.into_iter().map(|c| c.into()).collect())
+}
+
+/// MySQL's `FIELD(val, list...)`: the 1-based position of `val` in `list`, or 0.
+pub fn field<T: Into<FuncArg>>(val: T, list: Vec<FuncArg>) -> Col {
+    let mut args = vec![val.into()];
+    args.extend(list);
+    func("FIELD", args)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchMode {
+    NaturalLanguage,
+    NaturalLanguageWithQueryExpansion,
+    Boolean,
+    QueryExpansion,
+}
+
+impl ToSQL for SearchMode {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mode = match self {
+            SearchMode::NaturalLanguage => "IN NATURAL LANGUAGE MODE",
+            SearchMode::NaturalLanguageWithQueryExpansion => {
+                "IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION"
+            }
+            SearchMode::Boolean => "IN BOOLEAN MODE",
+            SearchMode::QueryExpansion => "WITH QUERY EXPANSION",
+        };
+        (String::from(mode), None)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchAgainst {
+    cols: Vec<Col>,
+    search: Arg,
+    mode: SearchMode,
+}
+
+/// `MATCH (cols...) AGAINST (? mode)`, the relevance score of a FULLTEXT search.
+/// Use it as a select column or, through `Exp::pred`, as a where predicate.
+pub fn match_against<T: ToArg>(cols: Vec<Col>, search: T, mode: SearchMode) -> Col {
+    MatchAgainst {
+        cols: cols.into_iter().map(Col::without_alias).collect(),
+        search: search.to_arg(),
+        mode,
+    }
+    .into()
+}
+
+impl ToSQL for MatchAgainst {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mut col_sql = vec![];
+        let mut args = vec![];
+        self.cols.iter().for_each(|col| {
+            let (sql, a) = col.to_sql();
+            col_sql.push(sql);
+            args.extend(a.unwrap_or_default());
+        });
+        args.push(self.search.clone());
+        (
+            format!(
+                "MATCH ({}) AGAINST (? {})",
+                col_sql.join(", "),
+                self.mode.to_sql().0
+            ),
+            Some(args),
+        )
+    }
+}
+
+impl Subqueries for MatchAgainst {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.cols.subqueries(f);
+    }
+}
+
+/// The target types MySQL accepts in `CAST(... AS type)`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlType {
+    Binary(Option<u32>),
+    Char(Option<u32>),
+    NChar(Option<u32>),
+    Date,
+    DateTime(Option<u8>),
+    Time(Option<u8>),
+    Year,
+    Decimal(Option<(u8, u8)>),
+    Double,
+    Float,
+    Real,
+    Signed,
+    Unsigned,
+    Json,
+}
+
+impl ToSQL for SqlType {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let with_len = |name: &str, len: &Option<u32>| match len {
+            Some(len) => format!("{name}({len})"),
+            None => name.to_string(),
+        };
+        let with_fsp = |name: &str, fsp: &Option<u8>| match fsp {
+            Some(fsp) => format!("{name}({fsp})"),
+            None => name.to_string(),
+        };
+        let sql = match self {
+            SqlType::Binary(len) => with_len("BINARY", len),
+            SqlType::Char(len) => with_len("CHAR", len),
+            SqlType::NChar(len) => with_len("NCHAR", len),
+            SqlType::Date => String::from("DATE"),
+            SqlType::DateTime(fsp) => with_fsp("DATETIME", fsp),
+            SqlType::Time(fsp) => with_fsp("TIME", fsp),
+            SqlType::Year => String::from("YEAR"),
+            SqlType::Decimal(Some((m, d))) => format!("DECIMAL({m}, {d})"),
+            SqlType::Decimal(None) => String::from("DECIMAL"),
+            SqlType::Double => String::from("DOUBLE"),
+            SqlType::Float => String::from("FLOAT"),
+            SqlType::Real => String::from("REAL"),
+            SqlType::Signed => String::from("SIGNED"),
+            SqlType::Unsigned => String::from("UNSIGNED"),
+            SqlType::Json => String::from("JSON"),
+        };
+        (sql, None)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum CastTarget {
+    Type(SqlType),
+    Charset(String),
+}
+
+/// `CAST(val AS type)` or `CONVERT(val USING charset)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cast {
+    val: FuncArg,
+    target: CastTarget,
+}
+
+/// `CAST(val AS type)`; `val` may be a column, a bound value or an expression.
+pub fn cast<T: Into<FuncArg>>(val: T, to: SqlType) -> Col {
+    Cast {
+        val: val.into(),
+        target: CastTarget::Type(to),
+    }
+    .into()
+}
+
+/// `CONVERT(val USING charset)`, e.g. `convert_using(cl("user", "name"), "utf8mb4")`.
+pub fn convert_using<T: Into<FuncArg>>(val: T, charset: &'static str) -> Col {
+    Cast {
+        val: val.into(),
+        target: CastTarget::Charset(charset.to_string()),
+    }
+    .into()
+}
+
+impl ToSQL for Cast {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let (val_sql, val_args) = self.val.to_sql();
+        let sql = match &self.target {
+            CastTarget::Type(to) => format!("CAST({val_sql} AS {})", to.to_sql().0),
+            CastTarget::Charset(charset) => format!("CONVERT({val_sql} USING {charset})"),
+        };
+        (sql, val_args)
+    }
+}
+
+impl Subqueries for Cast {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.val.subqueries(f);
+    }
+}
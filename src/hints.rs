@@ -0,0 +1,128 @@
+use crate::args::*;
+use crate::traits::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IndexHintKind {
+    Use,
+    Force,
+    Ignore,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IndexHintScope {
+    Join,
+    OrderBy,
+    GroupBy,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexHint {
+    kind: IndexHintKind,
+    scope: Option<IndexHintScope>,
+    indexes: Vec<String>,
+}
+
+impl IndexHint {
+    pub fn new(kind: IndexHintKind, indexes: Vec<&'static str>) -> Self {
+        IndexHint {
+            kind,
+            scope: None,
+            indexes: indexes.iter().map(|i| i.to_string()).collect(),
+        }
+    }
+    pub fn for_join(mut self) -> Self {
+        self.scope = Some(IndexHintScope::Join);
+        self
+    }
+    pub fn for_order_by(mut self) -> Self {
+        self.scope = Some(IndexHintScope::OrderBy);
+        self
+    }
+    pub fn for_group_by(mut self) -> Self {
+        self.scope = Some(IndexHintScope::GroupBy);
+        self
+    }
+}
+
+impl ToSQL for IndexHint {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mut sql = match self.kind {
+            IndexHintKind::Use => String::from("USE INDEX"),
+            IndexHintKind::Force => String::from("FORCE INDEX"),
+            IndexHintKind::Ignore => String::from("IGNORE INDEX"),
+        };
+        match self.scope {
+            Some(IndexHintScope::Join) => sql.push_str(" FOR JOIN"),
+            Some(IndexHintScope::OrderBy) => sql.push_str(" FOR ORDER BY"),
+            Some(IndexHintScope::GroupBy) => sql.push_str(" FOR GROUP BY"),
+            None => (),
+        }
+        sql.push_str(format!(" ({})", quote_all(&self.indexes)).as_str());
+        (sql, None)
+    }
+}
+
+/// MySQL optimizer hints, rendered inside `/*+ ... */` right after `SELECT`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptimizerHint {
+    MaxExecutionTime(u32),
+    SetVar(&'static str, u64),
+    ResourceGroup(&'static str),
+    JoinFixedOrder,
+    JoinOrder(Vec<&'static str>),
+    JoinPrefix(Vec<&'static str>),
+    JoinSuffix(Vec<&'static str>),
+    Bka(Vec<&'static str>),
+    NoBka(Vec<&'static str>),
+    HashJoin(Vec<&'static str>),
+    NoHashJoin(Vec<&'static str>),
+    Merge(Vec<&'static str>),
+    NoMerge(Vec<&'static str>),
+    Index(&'static str, Vec<&'static str>),
+    NoIndex(&'static str, Vec<&'static str>),
+    IndexMerge(&'static str, Vec<&'static str>),
+    NoIndexMerge(&'static str, Vec<&'static str>),
+}
+
+impl ToSQL for OptimizerHint {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let table_hint = |name: &str, tables: &Vec<&'static str>| {
+            format!("{name}({})", quote_all(tables))
+        };
+        let index_hint = |name: &str, table: &str, indexes: &Vec<&'static str>| {
+            if indexes.is_empty() {
+                format!("{name}(`{table}`)")
+            } else {
+                format!("{name}(`{table}` {})", quote_all(indexes))
+            }
+        };
+        let sql = match self {
+            OptimizerHint::MaxExecutionTime(ms) => format!("MAX_EXECUTION_TIME({ms})"),
+            OptimizerHint::SetVar(var, val) => format!("SET_VAR({var}={val})"),
+            OptimizerHint::ResourceGroup(group) => format!("RESOURCE_GROUP(`{group}`)"),
+            OptimizerHint::JoinFixedOrder => String::from("JOIN_FIXED_ORDER()"),
+            OptimizerHint::JoinOrder(t) => table_hint("JOIN_ORDER", t),
+            OptimizerHint::JoinPrefix(t) => table_hint("JOIN_PREFIX", t),
+            OptimizerHint::JoinSuffix(t) => table_hint("JOIN_SUFFIX", t),
+            OptimizerHint::Bka(t) => table_hint("BKA", t),
+            OptimizerHint::NoBka(t) => table_hint("NO_BKA", t),
+            OptimizerHint::HashJoin(t) => table_hint("HASH_JOIN", t),
+            OptimizerHint::NoHashJoin(t) => table_hint("NO_HASH_JOIN", t),
+            OptimizerHint::Merge(t) => table_hint("MERGE", t),
+            OptimizerHint::NoMerge(t) => table_hint("NO_MERGE", t),
+            OptimizerHint::Index(t, i) => index_hint("INDEX", t, i),
+            OptimizerHint::NoIndex(t, i) => index_hint("NO_INDEX", t, i),
+            OptimizerHint::IndexMerge(t, i) => index_hint("INDEX_MERGE", t, i),
+            OptimizerHint::NoIndexMerge(t, i) => index_hint("NO_INDEX_MERGE", t, i),
+        };
+        (sql, None)
+    }
+}
+
+fn quote_all<T: AsRef<str>>(names: &[T]) -> String {
+    names
+        .iter()
+        .map(|n| format!("`{}`", n.as_ref()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
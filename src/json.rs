@@ -0,0 +1,154 @@
+use crate::args::*;
+use crate::expressions::*;
+use crate::mysql::MYSQLBuilder;
+use crate::table::*;
+use crate::traits::*;
+
+// MySQL's `col->path` and `col->>path` only accept a literal path, so the
+// accessors below render the equivalent function calls with the path bound.
+impl Col {
+    /// `col->path`, rendered as `JSON_EXTRACT(col, ?)`.
+    pub fn json(self, path: &str) -> Col {
+        func("JSON_EXTRACT", vec![self.into(), path.to_string().into()])
+    }
+    /// `col->>path`, rendered as `JSON_UNQUOTE(JSON_EXTRACT(col, ?))`.
+    pub fn json_text(self, path: &str) -> Col {
+        func("JSON_UNQUOTE", vec![self.json(path).into()])
+    }
+    pub fn json_contains<T: Into<FuncArg>>(self, candidate: T) -> Exp {
+        Exp::pred(func("JSON_CONTAINS", vec![self.into(), candidate.into()]))
+    }
+    pub fn json_contains_at<T: Into<FuncArg>>(self, candidate: T, path: &str) -> Exp {
+        Exp::pred(func(
+            "JSON_CONTAINS",
+            vec![self.into(), candidate.into(), path.to_string().into()],
+        ))
+    }
+    pub fn json_overlaps<T: Into<FuncArg>>(self, other: T) -> Exp {
+        Exp::pred(func("JSON_OVERLAPS", vec![self.into(), other.into()]))
+    }
+}
+
+/// `val MEMBER OF(array)`.
+pub fn member_of<T: ToExpTar>(val: T, array: Col) -> Exp {
+    Exp::Exp(ExpU::new(Op::MemberOf, val.to_exp_tar(), array.to_exp_tar()))
+}
+
+pub fn json_set<T: Into<FuncArg>>(doc: T, pairs: Vec<(&str, FuncArg)>) -> Col {
+    let mut args = vec![doc.into()];
+    pairs.into_iter().for_each(|(path, val)| {
+        args.push(path.to_string().into());
+        args.push(val);
+    });
+    func("JSON_SET", args)
+}
+
+pub fn json_remove<T: Into<FuncArg>>(doc: T, paths: Vec<&str>) -> Col {
+    let mut args = vec![doc.into()];
+    args.extend(paths.into_iter().map(|p| p.to_string().into()));
+    func("JSON_REMOVE", args)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonColumn {
+    Path {
+        name: String,
+        col_type: &'static str,
+        path: String,
+    },
+    Exists {
+        name: String,
+        col_type: &'static str,
+        path: String,
+    },
+    Ordinality(String),
+}
+
+pub fn json_col(name: &'static str, col_type: &'static str, path: &str) -> JsonColumn {
+    JsonColumn::Path {
+        name: name.to_string(),
+        col_type,
+        path: path.to_string(),
+    }
+}
+
+pub fn json_exists_col(name: &'static str, col_type: &'static str, path: &str) -> JsonColumn {
+    JsonColumn::Exists {
+        name: name.to_string(),
+        col_type,
+        path: path.to_string(),
+    }
+}
+
+pub fn ordinality(name: &'static str) -> JsonColumn {
+    JsonColumn::Ordinality(name.to_string())
+}
+
+impl ToSQL for JsonColumn {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let sql = match self {
+            JsonColumn::Path {
+                name,
+                col_type,
+                path,
+            } => format!("`{name}` {col_type} PATH {}", quote_str(path)),
+            JsonColumn::Exists {
+                name,
+                col_type,
+                path,
+            } => format!("`{name}` {col_type} EXISTS PATH {}", quote_str(path)),
+            JsonColumn::Ordinality(name) => format!("`{name}` FOR ORDINALITY"),
+        };
+        (sql, None)
+    }
+}
+
+/// A `JSON_TABLE(doc, path COLUMNS(...))` source. The document is bound, but
+/// MySQL requires the row and column paths to be literals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonTable {
+    doc: FuncArg,
+    path: String,
+    columns: Vec<JsonColumn>,
+}
+
+pub fn json_table<T: Into<FuncArg>>(
+    doc: T,
+    path: &str,
+    columns: Vec<JsonColumn>,
+    alias: &'static str,
+) -> Table {
+    Table::json(
+        JsonTable {
+            doc: doc.into(),
+            path: path.to_string(),
+            columns,
+        },
+        alias,
+    )
+}
+
+impl ToSQL for JsonTable {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let (doc_sql, doc_args) = self.doc.to_sql();
+        let columns = self
+            .columns
+            .iter()
+            .map(|c| c.to_sql().0)
+            .collect::<Vec<String>>();
+        (
+            format!(
+                "JSON_TABLE({doc_sql}, {} COLUMNS ({}))",
+                quote_str(&self.path),
+                columns.join(", ")
+            ),
+            doc_args,
+        )
+    }
+}
+
+impl Subqueries for JsonTable {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.doc.subqueries(f);
+    }
+}
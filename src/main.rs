@@ -1,5 +1,12 @@
+#![allow(dead_code, clippy::wrong_self_convention)]
+
 mod args;
+mod dialect;
+mod error;
 mod expressions;
+mod functions;
+mod hints;
+mod json;
 mod mysql;
 mod statements;
 mod table;
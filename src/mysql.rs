@@ -1,9 +1,14 @@
 use crate::args::*;
+use crate::dialect::*;
+use crate::error::*;
 use crate::expressions::*;
+use crate::functions::*;
+use crate::hints::*;
 use crate::statements::*;
 use crate::table::*;
 use crate::traits::*;
 
+#[derive(Clone, Debug, PartialEq)]
 enum QueryType {
     Select,
     Insert,
@@ -11,18 +16,26 @@ enum QueryType {
     Update,
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct MYSQLBuilder {
     from: Option<Table>,
     select: Option<Select>,
     joins: Vec<Join>,
-    unions: Vec<MYSQLBuilder>,
+    unions: Vec<(SetOp, MYSQLBuilder)>,
+    compound_order: Vec<Order>,
+    compound_limit: Option<Limit>,
     r#where: Option<Where>,
     set: Option<Set>,
     insert: Option<Insert>,
     order: Vec<Order>,
     limit: Option<Limit>,
     group_by: Option<GroupBy>,
+    having: Option<Having>,
+    returning: Option<Returning>,
+    targets: Vec<Table>,
+    lock: Option<Lock>,
     query_type: QueryType,
+    dialect: Dialect,
 }
 
 impl QueryBuilder for MYSQLBuilder {
@@ -32,31 +45,59 @@ impl QueryBuilder for MYSQLBuilder {
             select: None,
             joins: vec![],
             unions: vec![],
+            compound_order: vec![],
+            compound_limit: None,
             r#where: None,
             set: None,
             insert: None,
             order: vec![],
             limit: None,
             group_by: None,
+            having: None,
+            returning: None,
+            targets: vec![],
+            lock: None,
             query_type: QueryType::Select,
+            dialect: Dialect::MySQL,
         }
     }
-    fn to_sql(&self) -> (String, Vec<Arg>) {
-        match self.query_type {
-            QueryType::Select => self.to_select_sql(),
-            QueryType::Update => self.to_update_sql(),
-            QueryType::Insert => self.to_insert_sql(),
-            QueryType::Delete => self.to_delete_sql(),
-        }
+    fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+    fn build(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        let mut query = self.clone();
+        query.prepare(self.dialect)?;
+        let (sql, args) = query.render()?;
+        Ok((self.dialect.finish_sql(sql), args))
+    }
+}
+
+impl Subqueries for MYSQLBuilder {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.from.subqueries(f);
+        self.select.subqueries(f);
+        self.joins.subqueries(f);
+        self.unions.iter_mut().for_each(|(_, query)| f(query));
+        self.compound_order.subqueries(f);
+        self.r#where.subqueries(f);
+        self.set.subqueries(f);
+        self.insert.subqueries(f);
+        self.order.subqueries(f);
+        self.group_by.subqueries(f);
+        self.having.subqueries(f);
+        self.returning.subqueries(f);
+        self.targets.subqueries(f);
     }
 }
 
 impl FetchQBuilder for MYSQLBuilder {
-    fn from(mut self, table_name: &'static str) -> Self {
-        self.from = Some(Table::new(table_name.to_string()));
+    fn from<T: Into<Table>>(mut self, table: T) -> Self {
+        self.from = Some(table.into());
         self
     }
-    fn select(mut self, cols: Vec<Col>) -> Self {
+    fn select<T: Into<SelectItem>>(mut self, cols: Vec<T>) -> Self {
+        let cols = cols.into_iter().map(|c| c.into()).collect();
         if let Some(mut select) = self.select {
             select.extend(cols);
             self.select = Some(select)
@@ -66,6 +107,21 @@ impl FetchQBuilder for MYSQLBuilder {
         self.query_type = QueryType::Select;
         self
     }
+    /// Swaps the select list, keeping joins, filters, grouping, `DISTINCT` and hints.
+    /// On a compound query only the first branch changes, so `build` fails unless the others match it.
+    fn replace_select<T: Into<SelectItem>>(mut self, cols: Vec<T>) -> Self {
+        let cols = cols.into_iter().map(|c| c.into()).collect();
+        match &mut self.select {
+            Some(select) => select.set_cols(cols),
+            None => self.select = Some(Select::new(cols)),
+        }
+        self.query_type = QueryType::Select;
+        self
+    }
+    /// Selects just `col`, e.g. to reuse a filtered query as an `IN (SELECT ...)` subquery.
+    fn pluck(self, col: Col) -> Self {
+        self.replace_select(vec![col])
+    }
     fn distinct(mut self) -> Self {
         if let Some(mut select) = self.select {
             select.distinct();
@@ -73,17 +129,53 @@ impl FetchQBuilder for MYSQLBuilder {
         }
         self
     }
+    fn hint(mut self, hint: OptimizerHint) -> Self {
+        let mut select = self.select.take().unwrap_or_else(|| Select::new(vec![]));
+        select.hint(hint);
+        self.select = Some(select);
+        self
+    }
     fn join(self, table: Table, on: On) -> Self {
         self.do_join(table, *on.exp, JoinType::Inner)
     }
     fn left_join(self, table: Table, on: Exp) -> Self {
-        self.do_join(table, on, JoinType::Inner)
+        self.do_join(table, on, JoinType::Left)
     }
     fn right_join(self, table: Table, on: Exp) -> Self {
-        self.do_join(table, on, JoinType::Inner)
+        self.do_join(table, on, JoinType::Right)
+    }
+    fn cross_join(mut self, table: Table) -> Self {
+        self.joins.push(Join::new(table, JoinType::Cross, None));
+        self
+    }
+    fn join_lateral(self, query: Self, alias: &'static str, on: Option<Exp>) -> Self {
+        self.do_lateral_join(query, alias, on, JoinType::Inner)
+    }
+    fn left_join_lateral(self, query: Self, alias: &'static str, on: Option<Exp>) -> Self {
+        self.do_lateral_join(query, alias, on, JoinType::Left)
     }
     fn union(mut self, query: Self) -> Self {
-        self.unions.push(query);
+        self.unions.push((SetOp::Union, query));
+        self
+    }
+    fn union_all(mut self, query: Self) -> Self {
+        self.unions.push((SetOp::UnionAll, query));
+        self
+    }
+    fn intersect(mut self, query: Self) -> Self {
+        self.unions.push((SetOp::Intersect, query));
+        self
+    }
+    fn except(mut self, query: Self) -> Self {
+        self.unions.push((SetOp::Except, query));
+        self
+    }
+    fn compound_order<T: Into<OrderBy>>(mut self, by: T, dir: Dir) -> Self {
+        self.compound_order.push(Order::new(by, dir));
+        self
+    }
+    fn compound_limit(mut self, by: i32) -> Self {
+        self.compound_limit = Some(Limit::new(by));
         self
     }
     fn group_by(mut self, by: Col) -> Self {
@@ -96,21 +188,76 @@ impl FetchQBuilder for MYSQLBuilder {
         };
         self
     }
-    fn having(mut self, exp: ExpU) -> Self {
-        if let Some(mut group_by) = self.group_by {
-            group_by.having(exp);
-            self.group_by = Some(group_by);
-        }
+    fn with_rollup(self) -> Self {
+        self.do_group_by(|group_by| group_by.with_rollup())
+    }
+    fn rollup(self, cols: Vec<Col>) -> Self {
+        self.do_group_by(|group_by| group_by.set(GroupingSet::Rollup(cols)))
+    }
+    fn cube(self, cols: Vec<Col>) -> Self {
+        self.do_group_by(|group_by| group_by.set(GroupingSet::Cube(cols)))
+    }
+    fn grouping_sets(self, sets: Vec<Vec<Col>>) -> Self {
+        self.do_group_by(|group_by| group_by.set(GroupingSet::Sets(sets)))
+    }
+    fn having(mut self, exp: Exp) -> Self {
+        match self.having {
+            Some(having) => self.having = Some(Having::new(having.exp.and(exp))),
+            None => self.having = Some(Having::new(exp)),
+        };
         self
     }
-    fn order(mut self, by: Col, dir: Dir) -> Self {
+    fn order<T: Into<OrderBy>>(mut self, by: T, dir: Dir) -> Self {
         self.order.push(Order::new(by, dir));
         self
     }
+    fn order_by(mut self, order: Order) -> Self {
+        self.order.push(order);
+        self
+    }
+    fn order_by_field<T: ToArg>(mut self, col: Col, values: Vec<T>) -> Self {
+        let values = values.into_iter().map(|v| v.to_arg().into()).collect();
+        self.order.push(Order::new(field(col, values), Dir::Asc));
+        self
+    }
     fn limit(mut self, by: i32) -> Self {
         self.limit = Some(Limit::new(by));
         self
     }
+    /// Drops the `ORDER BY`; on a compound query, only the one over the whole result.
+    fn clear_order(mut self) -> Self {
+        if self.unions.is_empty() {
+            self.order.clear();
+        }
+        self.compound_order.clear();
+        self
+    }
+    /// Drops the `LIMIT`; on a compound query, only the one over the whole result.
+    fn clear_limit(mut self) -> Self {
+        if self.unions.is_empty() {
+            self.limit = None;
+        }
+        self.compound_limit = None;
+        self
+    }
+    fn for_update(self) -> Self {
+        self.do_lock(|lock| lock.strength = Some(LockStrength::Update))
+    }
+    fn for_share(self) -> Self {
+        self.do_lock(|lock| lock.strength = Some(LockStrength::Share))
+    }
+    fn lock_in_share_mode(self) -> Self {
+        self.do_lock(|lock| lock.strength = Some(LockStrength::ShareMode))
+    }
+    fn nowait(self) -> Self {
+        self.do_lock(|lock| lock.wait = Some(LockWait::Nowait))
+    }
+    fn skip_locked(self) -> Self {
+        self.do_lock(|lock| lock.wait = Some(LockWait::SkipLocked))
+    }
+    fn of(self, tables: Vec<Table>) -> Self {
+        self.do_lock(|lock| lock.of.extend(tables))
+    }
 }
 
 impl WhereQBuilder for MYSQLBuilder {
@@ -125,6 +272,23 @@ impl WhereQBuilder for MYSQLBuilder {
         };
         self
     }
+    fn clear_where(mut self) -> Self {
+        self.r#where = None;
+        self
+    }
+}
+
+impl ReturningQBuilder for MYSQLBuilder {
+    fn returning(mut self, cols: Vec<Col>) -> Self {
+        match self.returning {
+            Some(mut returning) => {
+                returning.extend(cols);
+                self.returning = Some(returning);
+            }
+            None => self.returning = Some(Returning::new(cols)),
+        };
+        self
+    }
 }
 
 impl UpdateQBuilder for MYSQLBuilder {
@@ -145,6 +309,10 @@ impl DeleteQBuilder for MYSQLBuilder {
         self.query_type = QueryType::Delete;
         self
     }
+    fn targets(mut self, tables: Vec<Table>) -> Self {
+        self.targets.extend(tables);
+        self
+    }
 }
 
 impl InsertQBuilder for MYSQLBuilder {
@@ -153,10 +321,13 @@ impl InsertQBuilder for MYSQLBuilder {
         self.query_type = QueryType::Insert;
         self
     }
-    fn rows(mut self, keys: Vec<&'static str>, values: Vec<Vec<Arg>>) -> Self {
+    fn rows<T: ToExpTar>(mut self, keys: Vec<&'static str>, values: Vec<Vec<T>>) -> Self {
         self.insert = Some(Insert::new(
             keys.iter().map(|k| k.to_string()).collect(),
-            values,
+            values
+                .into_iter()
+                .map(|row| row.into_iter().map(|val| val.to_exp_tar()).collect())
+                .collect(),
         ));
         self
     }
@@ -169,92 +340,509 @@ impl MYSQLBuilder {
             from: None,
             joins: vec![],
             unions: vec![],
+            compound_order: vec![],
+            compound_limit: None,
             r#where: None,
             set: None,
             insert: None,
             order: vec![],
             limit: None,
             group_by: None,
+            having: None,
+            returning: None,
+            targets: vec![],
+            lock: None,
             query_type: QueryType::Select,
+            dialect: Dialect::MySQL,
         }
     }
 
-    fn to_select_sql(&self) -> (String, Vec<Arg>) {
-        let (mut query, mut args) = self.unpack_element(&self.select);
-        let (from_query, from_args) = self.unpack_element(&self.from);
-        query.push_str(format!("\nFROM {from_query}").as_str());
-        args.extend(from_args);
-        for join in &self.joins {
-            let (join_query, join_args) = self.unpack_element_ref(&Some(join));
-            query.push_str(format!("\n{join_query}").as_str());
-            args.extend(join_args);
+    /// Renders a builder nested in another query. The outer `build()` has already handed it
+    /// its dialect and checked that it renders, so this only panics when called on its own.
+    pub fn nested_sql(&self) -> (String, Vec<Arg>) {
+        self.render().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Hands `dialect` down to every nested builder and renders each one once, so an invalid
+    /// subquery fails the outer `build()` instead of panicking halfway through rendering it.
+    fn prepare(&mut self, dialect: Dialect) -> Result<(), BuildError> {
+        self.dialect = dialect;
+        let mut result = Ok(());
+        self.subqueries(&mut |query| {
+            if result.is_ok() {
+                result = query.prepare(dialect).and_then(|_| query.render().map(|_| ()));
+            }
+        });
+        result
+    }
+
+    fn render(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        match self.query_type {
+            QueryType::Select => self.to_select_sql(),
+            QueryType::Update => self.to_update_sql(),
+            QueryType::Insert => self.to_insert_sql(),
+            QueryType::Delete => self.to_delete_sql(),
         }
-        for r#where in &self.r#where {
-            let (where_query, where_args) = self.unpack_element_ref(&Some(r#where));
-            query.push_str(format!("\n{where_query}").as_str());
-            args.extend(where_args);
+    }
+
+    /// `SELECT COUNT(*) AS count` over the rows this select returns, ignoring its order and limit.
+    /// Grouped and compound queries are counted through a derived table, whose columns get unique aliases
+    /// (or collapse to a single `COUNT(*)` when only `GROUP BY` needs the derived table).
+    pub fn to_count_query(&self) -> Self {
+        let mut query = self.without_paging();
+        if query.needs_derived_count() {
+            let keeps_cols = query.having.is_some() || !query.unions.is_empty();
+            if let Some(select) = query.select.as_mut() {
+                if keeps_cols || select.is_distinct() {
+                    select.alias_unnamed();
+                } else {
+                    select.set_cols(vec![count_all().into()]);
+                }
+            }
+            return MYSQLBuilder::query()
+                .dialect(self.dialect)
+                .from(Table::derived(query, "counted"))
+                .select(vec![count_all().as_alias("count")]);
+        }
+        let count = match &query.select {
+            Some(select) if select.is_distinct() => {
+                let cols = select
+                    .cols()
+                    .iter()
+                    .filter_map(|item| match item {
+                        SelectItem::C(col) => Some(col.clone().without_alias()),
+                        _ => None,
+                    })
+                    .collect();
+                count_distinct(cols)
+            }
+            _ => count_all(),
+        };
+        let mut select = query.select.take().unwrap_or(Select::new(vec![]));
+        select.clear_distinct();
+        select.set_cols(vec![count.as_alias("count").into()]);
+        query.select = Some(select);
+        query
+    }
+
+    /// `SELECT EXISTS (...)`: whether this select returns any row, ignoring its order and limit.
+    pub fn to_exists_query(&self) -> Self {
+        let mut query = self.without_paging();
+        if !query.needs_derived_count() {
+            let mut select = query.select.take().unwrap_or(Select::new(vec![]));
+            select.clear_distinct();
+            select.set_cols(vec![star()]);
+            query.select = Some(select);
+        }
+        MYSQLBuilder::query()
+            .dialect(self.dialect)
+            .select(vec![exp(exists(query))])
+    }
+
+    fn without_paging(&self) -> Self {
+        let mut query = self.clone();
+        // on a compound query `order` and `limit` page the first branch, not the result
+        if query.unions.is_empty() {
+            query.order.clear();
+            query.limit = None;
+        }
+        query.compound_order.clear();
+        query.compound_limit = None;
+        query.lock = None;
+        query
+    }
+
+    /// Whether counting rows means counting the rows of the whole query rather than rewriting its select list.
+    fn needs_derived_count(&self) -> bool {
+        let distinct_rewrite_fails = self.select.as_ref().is_some_and(|s| {
+            let non_cols = s.cols().iter().any(|item| !matches!(item, SelectItem::C(_)));
+            // Postgres has no multi-column COUNT(DISTINCT a, b)
+            let multi_col = s.cols().len() > 1 && self.dialect == Dialect::Postgres;
+            s.is_distinct() && (non_cols || multi_col)
+        });
+        self.group_by.is_some()
+            || self.having.is_some()
+            || !self.unions.is_empty()
+            || distinct_rewrite_fails
+    }
+
+    fn to_select_sql(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        self.check_lock()?;
+        self.check_hints()?;
+        self.check_sources()?;
+        self.check_grouping()?;
+        if self.unions.is_empty() {
+            let (mut query, args) = self.select_body_sql()?;
+            let (lock_query, _) = self.unpack_element(&self.lock);
+            if !lock_query.is_empty() {
+                query.push_str(format!("\n{lock_query}").as_str());
+            }
+            return Ok((query, args));
+        }
+        self.check_set_columns()?;
+        let (first_query, mut args) = self.select_body_sql()?;
+        let mut query = format!("({first_query})");
+        for (op, qb) in &self.unions {
+            let (op_query, _) = op.to_sql();
+            let (union_query, union_args) = qb.render()?;
+            query.push_str(format!("\n{op_query}\n({union_query})").as_str());
+            args.extend(union_args);
         }
         let mut order_query_strings = vec![];
-        for order in &self.order {
-            let (or_query, order_args) = self.unpack_element_ref(&Some(order));
+        for order in &self.compound_order {
+            let (or_query, order_args) = order.to_compound_sql(self.dialect);
             order_query_strings.push(or_query);
-            args.extend(order_args);
+            args.extend(order_args.unwrap_or_default());
+        }
+        if !order_query_strings.is_empty() {
+            query.push_str(format!("\nORDER BY {}", order_query_strings.join(", ")).as_str());
         }
-        if order_query_strings.len() > 0 {
-            let order_query = format!("\nORDER BY {}", order_query_strings.join(", "));
-            query.push_str(order_query.as_str());
+        let (limit_query, limit_args) = self.unpack_element(&self.compound_limit);
+        if !limit_query.is_empty() {
+            query.push_str(format!("\n{limit_query}").as_str());
+            args.extend(limit_args);
+        }
+        Ok((query, args))
+    }
+
+    fn select_body_sql(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        let (mut query, mut args) = self.unpack_element(&self.select);
+        if self.from.is_some() {
+            let (from_query, from_args) = self.unpack_element(&self.from);
+            query.push_str(format!("\nFROM {from_query}").as_str());
+            args.extend(from_args);
+        }
+        let (join_query, join_args) = self.joins_sql();
+        query.push_str(join_query.as_str());
+        args.extend(join_args);
+        if let Some(r#where) = &self.r#where {
+            let (where_query, where_args) = self.unpack_element_ref(&Some(r#where));
+            query.push_str(format!("\n{where_query}").as_str());
+            args.extend(where_args);
         }
         let (group_query, group_args) = self.unpack_element(&self.group_by);
-        if group_query.len() > 0 {
+        if !group_query.is_empty() {
             query.push_str(format!("\n{group_query}").as_str());
             args.extend(group_args);
         }
+        let (having_query, having_args) = self.unpack_element(&self.having);
+        if !having_query.is_empty() {
+            query.push_str(format!("\n{having_query}").as_str());
+            args.extend(having_args);
+        }
+        let (order_query, order_args) = self.order_sql()?;
+        query.push_str(order_query.as_str());
+        args.extend(order_args);
         let (limit_query, limit_args) = self.unpack_element(&self.limit);
-        if limit_query.len() > 0 {
+        if !limit_query.is_empty() {
             query.push_str(format!("\n{limit_query}").as_str());
             args.extend(limit_args);
         }
-        for qb in &self.unions {
-            let (union_query, union_args) = qb.to_sql();
-            query.push_str(format!("\nUNION\n{union_query}").as_str());
-            args.extend(union_args);
-        }
-        (query, args)
+        Ok((query, args))
     }
 
-    fn to_update_sql(&self) -> (String, Vec<Arg>) {
+    fn to_update_sql(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        if !self.joins.is_empty() && self.dialect == Dialect::Postgres {
+            return Err(BuildError::Unsupported {
+                feature: "UPDATE ... JOIN",
+                dialect: self.dialect,
+            });
+        }
+        if (!self.order.is_empty() || self.limit.is_some()) && self.dialect == Dialect::Postgres {
+            return Err(BuildError::Unsupported {
+                feature: "UPDATE ... ORDER BY/LIMIT",
+                dialect: self.dialect,
+            });
+        }
+        if !self.joins.is_empty() && (!self.order.is_empty() || self.limit.is_some()) {
+            return Err(BuildError::Invalid(
+                "ORDER BY and LIMIT can't be used with a multi-table UPDATE",
+            ));
+        }
         let (from_query, mut args) = self.unpack_element(&self.from);
-        let (set_query, set_args) = self.unpack_element(&self.set);
+        let mut query = format!("UPDATE {from_query}");
+        let (join_query, join_args) = self.joins_sql();
+        query.push_str(join_query.as_str());
+        args.extend(join_args);
+        let (set_query, set_args) = match (&self.set, self.dialect) {
+            (Some(set), Dialect::Postgres) => {
+                let (sql, args) = set.to_unqualified_sql();
+                (sql, args.unwrap_or_default())
+            }
+            _ => self.unpack_element(&self.set),
+        };
         args.extend(set_args);
-        let mut query = format!("UPDATE {from_query} \nSET {set_query}");
+        query.push_str(format!(" \nSET {set_query}").as_str());
         let (where_query, where_args) = self.unpack_element(&self.r#where);
-        if where_query.len() > 0 {
+        if !where_query.is_empty() {
             query.push_str(format!("\n{where_query}").as_str());
             args.extend(where_args);
         }
-        (query, args)
+        let (order_query, order_args) = self.order_sql()?;
+        query.push_str(order_query.as_str());
+        args.extend(order_args);
+        let (limit_query, limit_args) = self.unpack_element(&self.limit);
+        if !limit_query.is_empty() {
+            query.push_str(format!("\n{limit_query}").as_str());
+            args.extend(limit_args);
+        }
+        let (returning_query, returning_args) = self.returning_sql()?;
+        if !returning_query.is_empty() {
+            query.push_str(format!("\n{returning_query}").as_str());
+            args.extend(returning_args);
+        }
+        Ok((query, args))
     }
 
-    fn to_insert_sql(&self) -> (String, Vec<Arg>) {
+    fn to_insert_sql(&self) -> Result<(String, Vec<Arg>), BuildError> {
         let mut args = Vec::new();
         let (from_query, from_args) = self.unpack_element(&self.from);
         let (column_query, column_args) = self.unpack_element(&self.insert);
         args.extend(from_args);
         args.extend(column_args);
-        (format!("INSERT INTO {from_query} {column_query}"), args)
+        let mut query = format!("INSERT INTO {from_query} {column_query}");
+        let (returning_query, returning_args) = self.returning_sql()?;
+        if !returning_query.is_empty() {
+            query.push_str(format!("\n{returning_query}").as_str());
+            args.extend(returning_args);
+        }
+        Ok((query, args))
     }
 
-    fn to_delete_sql(&self) -> (String, Vec<Arg>) {
+    fn to_delete_sql(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        let multi_table = !self.targets.is_empty() || !self.joins.is_empty();
+        if multi_table && self.dialect == Dialect::Postgres {
+            return Err(BuildError::Unsupported {
+                feature: "multi-table DELETE",
+                dialect: self.dialect,
+            });
+        }
+        if (!self.order.is_empty() || self.limit.is_some()) && self.dialect == Dialect::Postgres {
+            return Err(BuildError::Unsupported {
+                feature: "DELETE ... ORDER BY/LIMIT",
+                dialect: self.dialect,
+            });
+        }
+        if multi_table && (!self.order.is_empty() || self.limit.is_some()) {
+            return Err(BuildError::Invalid(
+                "ORDER BY and LIMIT can't be used with a multi-table DELETE",
+            ));
+        }
         let (from_query, mut args) = self.unpack_element(&self.from);
+        let mut query = String::from("DELETE ");
+        if !self.targets.is_empty() {
+            let targets = self
+                .targets
+                .iter()
+                .map(|t| t.ref_sql())
+                .collect::<Vec<String>>();
+            query.push_str(format!("{} ", targets.join(", ")).as_str());
+        }
+        query.push_str(format!("FROM {from_query}").as_str());
+        let (join_query, join_args) = self.joins_sql();
+        query.push_str(join_query.as_str());
+        args.extend(join_args);
         let (where_query, where_args) = self.unpack_element(&self.r#where);
-        let mut query = format!("DELETE FROM {}", from_query);
-        if where_query.len() > 0 {
+        if !where_query.is_empty() {
             query.push_str(format!(" {where_query}").as_str());
             args.extend(where_args);
         }
+        let (order_query, order_args) = self.order_sql()?;
+        query.push_str(order_query.as_str());
+        args.extend(order_args);
+        let (limit_query, limit_args) = self.unpack_element(&self.limit);
+        if !limit_query.is_empty() {
+            query.push_str(format!("\n{limit_query}").as_str());
+            args.extend(limit_args);
+        }
+        let (returning_query, returning_args) = self.returning_sql()?;
+        if !returning_query.is_empty() {
+            query.push_str(format!(" {returning_query}").as_str());
+            args.extend(returning_args);
+        }
+        Ok((query, args))
+    }
+
+    fn check_lock(&self) -> Result<(), BuildError> {
+        let branch_locked = self.unions.iter().any(|(_, query)| query.lock.is_some());
+        if branch_locked || (self.lock.is_some() && !self.unions.is_empty()) {
+            return Err(BuildError::Invalid(
+                "row locking clauses can't be combined with set operations",
+            ));
+        }
+        let Some(lock) = &self.lock else {
+            return Ok(());
+        };
+        let unsupported = |feature| {
+            Err(BuildError::Unsupported {
+                feature,
+                dialect: self.dialect,
+            })
+        };
+        match (&lock.strength, self.dialect) {
+            (None, _) => Err(BuildError::Invalid(
+                "NOWAIT, SKIP LOCKED and OF need FOR UPDATE or FOR SHARE",
+            )),
+            (Some(LockStrength::ShareMode), _) if lock.has_modifiers() => Err(BuildError::Invalid(
+                "LOCK IN SHARE MODE doesn't take NOWAIT, SKIP LOCKED or OF",
+            )),
+            (Some(LockStrength::ShareMode), Dialect::Postgres) => unsupported("LOCK IN SHARE MODE"),
+            (Some(LockStrength::Share), Dialect::MariaDB) => unsupported("FOR SHARE"),
+            (_, Dialect::MariaDB) if !lock.of.is_empty() => unsupported("FOR UPDATE OF"),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_hints(&self) -> Result<(), BuildError> {
+        if self.dialect != Dialect::Postgres {
+            return Ok(());
+        }
+        if self.select.as_ref().is_some_and(|s| s.has_hints()) {
+            return Err(BuildError::Unsupported {
+                feature: "optimizer hints",
+                dialect: self.dialect,
+            });
+        }
+        let from_hints = self.from.as_ref().is_some_and(|t| t.has_hints());
+        if from_hints || self.joins.iter().any(|j| j.table().has_hints()) {
+            return Err(BuildError::Unsupported {
+                feature: "index hints",
+                dialect: self.dialect,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_sources(&self) -> Result<(), BuildError> {
+        if self.dialect == Dialect::MySQL {
+            return Ok(());
+        }
+        if self.dialect == Dialect::MariaDB && self.joins.iter().any(|j| j.table().is_lateral()) {
+            return Err(BuildError::Unsupported {
+                feature: "LATERAL derived tables",
+                dialect: self.dialect,
+            });
+        }
+        let from_values = self.from.as_ref().is_some_and(|t| t.is_values());
+        if from_values || self.joins.iter().any(|j| j.table().is_values()) {
+            return Err(BuildError::Unsupported {
+                feature: "VALUES ROW table constructors",
+                dialect: self.dialect,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_set_columns(&self) -> Result<(), BuildError> {
+        // `*` and `table.*` expand to a column count only the server knows
+        let width = |query: &MYSQLBuilder| {
+            let cols = query.select.as_ref().map(|s| s.cols()).unwrap_or_default();
+            let star = cols
+                .iter()
+                .any(|c| matches!(c, SelectItem::Star | SelectItem::TableStar(_)));
+            (!star).then_some(cols.len())
+        };
+        let Some(first) = width(self) else {
+            return Ok(());
+        };
+        let mismatch = self
+            .unions
+            .iter()
+            .any(|(_, query)| width(query).is_some_and(|n| n != first));
+        if mismatch {
+            return Err(BuildError::Invalid(
+                "every branch of a set operation must select the same number of columns",
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_grouping(&self) -> Result<(), BuildError> {
+        let Some(group_by) = &self.group_by else {
+            return Ok(());
+        };
+        if group_by.is_with_rollup() && self.dialect == Dialect::Postgres {
+            return Err(BuildError::Unsupported {
+                feature: "WITH ROLLUP",
+                dialect: self.dialect,
+            });
+        }
+        if group_by.is_with_rollup() && !group_by.has_cols() {
+            return Err(BuildError::Invalid(
+                "WITH ROLLUP needs at least one GROUP BY column",
+            ));
+        }
+        if group_by.has_sets() && self.dialect != Dialect::Postgres {
+            return Err(BuildError::Unsupported {
+                feature: "GROUPING SETS, ROLLUP and CUBE",
+                dialect: self.dialect,
+            });
+        }
+        Ok(())
+    }
+
+    fn do_group_by(mut self, set: impl FnOnce(&mut GroupBy)) -> Self {
+        let mut group_by = self.group_by.take().unwrap_or_else(|| GroupBy::new(vec![]));
+        set(&mut group_by);
+        self.group_by = Some(group_by);
+        self
+    }
+
+    fn do_lock(mut self, set: impl FnOnce(&mut Lock)) -> Self {
+        let mut lock = self.lock.take().unwrap_or_else(Lock::new);
+        set(&mut lock);
+        self.lock = Some(lock);
+        self
+    }
+
+    fn joins_sql(&self) -> (String, Vec<Arg>) {
+        let mut query = String::from("");
+        let mut args = vec![];
+        for join in &self.joins {
+            let (join_query, join_args) = self.unpack_element_ref(&Some(join));
+            query.push_str(format!("\n{join_query}").as_str());
+            args.extend(join_args);
+        }
         (query, args)
     }
 
+    fn order_sql(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        let mut order_query_strings = vec![];
+        let mut args = vec![];
+        for order in &self.order {
+            if order.is_positional() && order.needs_null_emulation(self.dialect) {
+                return Err(BuildError::Invalid(
+                    "NULLS FIRST/LAST can't be emulated for positional ORDER BY",
+                ));
+            }
+            let (or_query, order_args) = order.to_dialect_sql(self.dialect);
+            order_query_strings.push(or_query);
+            args.extend(order_args.unwrap_or_default());
+        }
+        if order_query_strings.is_empty() {
+            return Ok((String::from(""), args));
+        }
+        Ok((format!("\nORDER BY {}", order_query_strings.join(", ")), args))
+    }
+
+    fn returning_sql(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        if self.returning.is_none() {
+            return Ok((String::from(""), vec![]));
+        }
+        // MariaDB has INSERT/DELETE ... RETURNING but no UPDATE ... RETURNING
+        let supported = matches!(
+            (self.dialect, &self.query_type),
+            (Dialect::Postgres, _) | (Dialect::MariaDB, QueryType::Insert | QueryType::Delete)
+        );
+        if !supported {
+            return Err(BuildError::Unsupported {
+                feature: "RETURNING",
+                dialect: self.dialect,
+            });
+        }
+        Ok(self.unpack_element(&self.returning))
+    }
+
     fn unpack_element<T>(&self, element: &Option<T>) -> (String, Vec<Arg>)
     where
         T: ToSQL,
@@ -291,4 +879,19 @@ impl MYSQLBuilder {
         self.joins.push(Join::new(table, join, Some(On::new(on))));
         self
     }
+
+    fn do_lateral_join(
+        mut self,
+        query: Self,
+        alias: &'static str,
+        on: Option<Exp>,
+        join: JoinType,
+    ) -> Self {
+        self.joins.push(Join::new(
+            Table::lateral(query, alias),
+            join,
+            on.map(On::new),
+        ));
+        self
+    }
 }
@@ -1,8 +1,12 @@
 use crate::args::*;
+use crate::dialect::*;
 use crate::expressions::*;
+use crate::hints::*;
+use crate::mysql::*;
 use crate::table::*;
 use crate::traits::*;
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct Limit(i32);
 impl Limit {
     pub fn new(by: i32) -> Self {
@@ -15,23 +19,109 @@ impl ToSQL for Limit {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct Returning(Vec<Col>);
+impl Returning {
+    pub fn new(cols: Vec<Col>) -> Self {
+        Returning(cols)
+    }
+    pub fn extend(&mut self, cols: Vec<Col>) {
+        self.0.extend(cols);
+    }
+}
+impl ToSQL for Returning {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mut col_sql = vec![];
+        let mut args = vec![];
+        self.0.iter().for_each(|col| {
+            let (sql, a) = col.to_sql();
+            col_sql.push(sql);
+            if let Some(v) = a {
+                args.extend(v);
+            }
+        });
+        (format!("RETURNING {}", col_sql.join(", ")), Some(args))
+    }
+}
+
+impl Subqueries for Returning {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.0.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GroupingSet {
+    Rollup(Vec<Col>),
+    Cube(Vec<Col>),
+    Sets(Vec<Vec<Col>>),
+}
+
+impl ToSQL for GroupingSet {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mut args = vec![];
+        let mut list = |cols: &Vec<Col>| {
+            let mut col_sql = vec![];
+            cols.iter().for_each(|col| {
+                let (sql, a) = col.to_sql();
+                col_sql.push(sql);
+                args.extend(a.unwrap_or_default());
+            });
+            format!("({})", col_sql.join(", "))
+        };
+        let sql = match self {
+            GroupingSet::Rollup(cols) => format!("ROLLUP {}", list(cols)),
+            GroupingSet::Cube(cols) => format!("CUBE {}", list(cols)),
+            GroupingSet::Sets(sets) => {
+                let sets_sql = sets.iter().map(list).collect::<Vec<String>>();
+                format!("GROUPING SETS ({})", sets_sql.join(", "))
+            }
+        };
+        (sql, Some(args))
+    }
+}
+
+impl Subqueries for GroupingSet {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match self {
+            GroupingSet::Rollup(cols) | GroupingSet::Cube(cols) => cols.subqueries(f),
+            GroupingSet::Sets(sets) => sets.subqueries(f),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct GroupBy {
     cols: Vec<Col>,
-    having: Option<Box<ExpU>>,
+    sets: Vec<GroupingSet>,
+    with_rollup: bool,
 }
 
 impl GroupBy {
     pub fn new(cols: Vec<Col>) -> Self {
         GroupBy {
-            cols: cols,
-            having: None,
+            cols,
+            sets: vec![],
+            with_rollup: false,
         }
     }
     pub fn extend(&mut self, cols: Vec<Col>) {
         self.cols.extend(cols);
     }
-    pub fn having(&mut self, exp: ExpU) {
-        self.having = Some(Box::new(exp));
+    pub fn set(&mut self, set: GroupingSet) {
+        self.sets.push(set);
+    }
+    pub fn has_sets(&self) -> bool {
+        !self.sets.is_empty()
+    }
+    pub fn has_cols(&self) -> bool {
+        !self.cols.is_empty()
+    }
+    pub fn with_rollup(&mut self) {
+        self.with_rollup = true;
+    }
+    pub fn is_with_rollup(&self) -> bool {
+        self.with_rollup
     }
 }
 
@@ -46,43 +136,225 @@ impl ToSQL for GroupBy {
             }
             (sql, None) => col_sql.push(sql),
         });
+        self.sets.iter().for_each(|set| {
+            let (sql, args) = set.to_sql();
+            col_sql.push(sql);
+            col_args.extend(args.unwrap_or_default());
+        });
         let mut sql = format!("GROUP BY {}", col_sql.join(", "));
-        if let Some(having) = &self.having {
-            match having.to_sql() {
-                (having_sql, Some(having_args)) => {
-                    sql.push_str(format!("HAVING {having_sql}").as_str());
-                    col_args.extend(having_args);
-                }
-                (having_sql, None) => sql.push_str(format!("HAVING {having_sql}").as_str()),
-            };
+        if self.with_rollup {
+            sql.push_str(" WITH ROLLUP");
         }
         (sql, Some(col_args))
     }
 }
 
+impl Subqueries for GroupBy {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.cols.subqueries(f);
+        self.sets.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Having {
+    pub exp: Box<Exp>,
+}
+
+impl Having {
+    pub fn new(exp: Exp) -> Self {
+        Having { exp: Box::new(exp) }
+    }
+}
+impl ToSQL for Having {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let (exp_sql, exp_args) = self.exp.to_sql();
+        (format!("HAVING ({exp_sql})"), exp_args)
+    }
+}
+
+impl Subqueries for Having {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.exp.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectItem {
+    C(Col),
+    Star,
+    TableStar(String),
+    Lit(Arg, Option<String>),
+    Sub(Box<MYSQLBuilder>, Option<String>),
+    E(Exp, Option<String>),
+}
+
+pub fn star() -> SelectItem {
+    SelectItem::Star
+}
+
+pub fn lit<T: ToArg>(val: T) -> SelectItem {
+    SelectItem::Lit(val.to_arg(), None)
+}
+
+pub fn sub(query: MYSQLBuilder) -> SelectItem {
+    SelectItem::Sub(Box::new(query), None)
+}
+
+pub fn exp(exp: Exp) -> SelectItem {
+    SelectItem::E(exp, None)
+}
+
+impl From<Col> for SelectItem {
+    fn from(col: Col) -> Self {
+        SelectItem::C(col)
+    }
+}
+
+impl SelectItem {
+    pub fn as_alias(self, val: &'static str) -> Self {
+        let alias = Some(val.to_string());
+        match self {
+            SelectItem::C(col) => SelectItem::C(col.as_alias(val)),
+            SelectItem::Lit(arg, _) => SelectItem::Lit(arg, alias),
+            SelectItem::Sub(query, _) => SelectItem::Sub(query, alias),
+            SelectItem::E(exp, _) => SelectItem::E(exp, alias),
+            // `*` and `table.*` can't be aliased
+            item => item,
+        }
+    }
+    /// Sets a crate-generated alias; public callers go through `as_alias` and its static names.
+    pub(crate) fn set_alias(&mut self, val: String) {
+        match self {
+            SelectItem::C(col) => col.set_alias(val),
+            SelectItem::Lit(_, alias) | SelectItem::Sub(_, alias) | SelectItem::E(_, alias) => {
+                *alias = Some(val)
+            }
+            SelectItem::Star | SelectItem::TableStar(_) => {}
+        }
+    }
+    pub fn alias(&self) -> Option<&str> {
+        match self {
+            SelectItem::C(col) => col.alias(),
+            SelectItem::Lit(_, alias) | SelectItem::Sub(_, alias) | SelectItem::E(_, alias) => {
+                alias.as_deref()
+            }
+            SelectItem::Star | SelectItem::TableStar(_) => None,
+        }
+    }
+}
+
+impl ToSQL for SelectItem {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let with_alias = |sql: String, alias: &Option<String>| match alias {
+            Some(val) => format!("{sql} AS {val}"),
+            None => sql,
+        };
+        match self {
+            SelectItem::C(col) => col.to_sql(),
+            SelectItem::Star => (String::from("*"), None),
+            SelectItem::TableStar(table) => (format!("{table}.*"), None),
+            SelectItem::Lit(arg, alias) => (with_alias(String::from("?"), alias), Some(vec![arg.clone()])),
+            SelectItem::Sub(query, alias) => {
+                let (sub_sql, sub_args) = query.nested_sql();
+                (with_alias(format!("({sub_sql})"), alias), Some(sub_args))
+            }
+            SelectItem::E(exp, alias) => {
+                let (exp_sql, exp_args) = exp.to_sql();
+                (with_alias(format!("({exp_sql})"), alias), exp_args)
+            }
+        }
+    }
+}
+
+impl Subqueries for SelectItem {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match self {
+            SelectItem::C(col) => col.subqueries(f),
+            SelectItem::Sub(query, _) => f(query),
+            SelectItem::E(exp, _) => exp.subqueries(f),
+            SelectItem::Star | SelectItem::TableStar(_) | SelectItem::Lit(..) => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Select {
-    cols: Vec<Col>,
+    cols: Vec<SelectItem>,
     distinct: bool,
+    hints: Vec<OptimizerHint>,
 }
 
 impl Select {
-    pub fn new(cols: Vec<Col>) -> Self {
+    pub fn new(cols: Vec<SelectItem>) -> Self {
         Select {
-            cols: cols,
+            cols,
             distinct: false,
+            hints: vec![],
         }
     }
-    pub fn extend(&mut self, cols: Vec<Col>) {
+    pub fn hint(&mut self, hint: OptimizerHint) {
+        self.hints.push(hint);
+    }
+    pub fn has_hints(&self) -> bool {
+        !self.hints.is_empty()
+    }
+    pub fn extend(&mut self, cols: Vec<SelectItem>) {
         self.cols.extend(cols);
     }
     pub fn distinct(&mut self) {
         self.distinct = true;
     }
+    pub fn is_distinct(&self) -> bool {
+        self.distinct
+    }
+    pub fn cols(&self) -> &[SelectItem] {
+        &self.cols
+    }
+    /// Swaps the select list, keeping `DISTINCT` and optimizer hints.
+    pub fn set_cols(&mut self, cols: Vec<SelectItem>) {
+        self.cols = cols;
+    }
+    pub fn clear_distinct(&mut self) {
+        self.distinct = false;
+    }
+    /// Gives every unaliased item a generated `_cN` alias, so no two result columns share a name.
+    pub fn alias_unnamed(&mut self) {
+        let mut taken: Vec<String> = self
+            .cols
+            .iter()
+            .filter_map(|item| item.alias().map(String::from))
+            .collect();
+        let mut n = 0;
+        for item in self.cols.iter_mut() {
+            let unaliasable = matches!(item, SelectItem::Star | SelectItem::TableStar(_));
+            if unaliasable || item.alias().is_some() {
+                continue;
+            }
+            let alias = loop {
+                n += 1;
+                let candidate = format!("_c{n}");
+                if !taken.contains(&candidate) {
+                    break candidate;
+                }
+            };
+            taken.push(alias.clone());
+            item.set_alias(alias);
+        }
+    }
 }
 
 impl ToSQL for Select {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
         let mut query = String::from("SELECT ");
+        if !self.hints.is_empty() {
+            let hints = self
+                .hints
+                .iter()
+                .map(|h| h.to_sql().0)
+                .collect::<Vec<String>>();
+            query.push_str(format!("/*+ {} */ ", hints.join(" ")).as_str());
+        }
         if self.distinct {
             query.push_str("DISTINCT ");
         }
@@ -100,11 +372,18 @@ impl ToSQL for Select {
     }
 }
 
-#[derive(Clone)]
+impl Subqueries for Select {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.cols.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum JoinType {
     Inner,
     Left,
     Right,
+    Cross,
     Union,
 }
 
@@ -114,11 +393,13 @@ impl From<JoinType> for String {
             JoinType::Inner => String::from("JOIN"),
             JoinType::Left => String::from("LEFT JOIN"),
             JoinType::Right => String::from("RIGHT JOIN"),
+            JoinType::Cross => String::from("CROSS JOIN"),
             _ => String::from("JOIN"),
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct Join {
     from: Table,
     join: JoinType,
@@ -129,12 +410,16 @@ impl Join {
     pub fn new(from: Table, join: JoinType, on: Option<On>) -> Self {
         Join { from, join, on }
     }
+    pub fn table(&self) -> &Table {
+        &self.from
+    }
 }
 impl ToSQL for Join {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
         let join_str: String = self.join.clone().into();
         let mut args = vec![];
-        let (from_sql, _) = self.from.to_sql();
+        let (from_sql, from_args) = self.from.to_sql();
+        args.extend(from_args.unwrap_or_default());
         let mut sql = format!("{join_str} {from_sql}");
         if let Some(on) = &self.on {
             let (exp_sql, exp_args_op) = on.to_sql();
@@ -142,11 +427,22 @@ impl ToSQL for Join {
                 args.extend(exp_args)
             }
             sql.push_str(format!(" {exp_sql}").as_str());
+        } else if self.from.is_lateral() && !matches!(self.join, JoinType::Cross) {
+            // the correlation lives inside the subquery, but JOIN still needs a condition
+            sql.push_str(" ON TRUE");
         };
         (sql, Some(args))
     }
 }
 
+impl Subqueries for Join {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.from.subqueries(f);
+        self.on.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct On {
     pub exp: Box<Exp>,
 }
@@ -162,6 +458,13 @@ impl ToSQL for On {
         (format!("ON ({exp_sql})"), exp_args)
     }
 }
+
+impl Subqueries for On {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.exp.subqueries(f);
+    }
+}
+#[derive(Clone, Debug, PartialEq)]
 pub struct Where {
     pub exp: Box<Exp>,
 }
@@ -178,7 +481,90 @@ impl ToSQL for Where {
     }
 }
 
-#[derive(Clone)]
+impl Subqueries for Where {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.exp.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LockStrength {
+    Update,
+    Share,
+    ShareMode,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LockWait {
+    Nowait,
+    SkipLocked,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lock {
+    pub strength: Option<LockStrength>,
+    pub of: Vec<Table>,
+    pub wait: Option<LockWait>,
+}
+
+impl Lock {
+    pub fn new() -> Self {
+        Lock {
+            strength: None,
+            of: vec![],
+            wait: None,
+        }
+    }
+    pub fn has_modifiers(&self) -> bool {
+        !self.of.is_empty() || self.wait.is_some()
+    }
+}
+
+impl ToSQL for Lock {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mut sql = match self.strength {
+            Some(LockStrength::Update) => String::from("FOR UPDATE"),
+            Some(LockStrength::Share) => String::from("FOR SHARE"),
+            Some(LockStrength::ShareMode) => String::from("LOCK IN SHARE MODE"),
+            None => String::from(""),
+        };
+        if !self.of.is_empty() {
+            let tables = self
+                .of
+                .iter()
+                .map(|t| t.ref_sql())
+                .collect::<Vec<String>>();
+            sql.push_str(format!(" OF {}", tables.join(", ")).as_str());
+        }
+        match self.wait {
+            Some(LockWait::Nowait) => sql.push_str(" NOWAIT"),
+            Some(LockWait::SkipLocked) => sql.push_str(" SKIP LOCKED"),
+            None => (),
+        }
+        (sql, None)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SetOp {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl ToSQL for SetOp {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        match self {
+            SetOp::Union => (String::from("UNION"), None),
+            SetOp::UnionAll => (String::from("UNION ALL"), None),
+            SetOp::Intersect => (String::from("INTERSECT"), None),
+            SetOp::Except => (String::from("EXCEPT"), None),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Dir {
     Asc,
     Desc,
@@ -193,20 +579,136 @@ impl ToSQL for Dir {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderBy {
+    C(Col),
+    Alias(String),
+    Position(usize),
+}
+
+pub fn by_alias(alias: &'static str) -> OrderBy {
+    OrderBy::Alias(alias.to_string())
+}
+
+pub fn by_position(position: usize) -> OrderBy {
+    OrderBy::Position(position)
+}
+
+impl From<Col> for OrderBy {
+    fn from(col: Col) -> Self {
+        OrderBy::C(col)
+    }
+}
+
+impl OrderBy {
+    fn to_compound_sql(&self) -> (String, Option<Vec<Arg>>) {
+        match self {
+            OrderBy::C(col) => col.ref_sql(),
+            _ => self.to_sql(),
+        }
+    }
+}
+
+impl ToSQL for OrderBy {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        match self {
+            // an aliased column is already in the select list, so order by its alias
+            OrderBy::C(col) if col.alias().is_some() => col.ref_sql(),
+            OrderBy::C(col) => col.to_sql(),
+            OrderBy::Alias(alias) => (format!("`{alias}`"), None),
+            OrderBy::Position(position) => (position.to_string(), None),
+        }
+    }
+}
+
+impl Subqueries for OrderBy {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        if let OrderBy::C(col) = self {
+            col.subqueries(f);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Order {
-    by: Col,
+    by: OrderBy,
     dir: Dir,
+    nulls: Option<Nulls>,
 }
 
 impl Order {
-    pub fn new(by: Col, dir: Dir) -> Self {
-        Order { by, dir }
+    pub fn new<T: Into<OrderBy>>(by: T, dir: Dir) -> Self {
+        Order {
+            by: by.into(),
+            dir,
+            nulls: None,
+        }
+    }
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = Some(Nulls::First);
+        self
+    }
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = Some(Nulls::Last);
+        self
+    }
+    /// MySQL has no `NULLS FIRST/LAST`, so it's emulated by ordering on `IS NULL`,
+    /// which can't be done for positional ordering.
+    pub fn needs_null_emulation(&self, dialect: Dialect) -> bool {
+        self.nulls.is_some() && dialect != Dialect::Postgres
+    }
+    pub fn is_positional(&self) -> bool {
+        matches!(self.by, OrderBy::Position(_))
+    }
+    pub fn to_dialect_sql(&self, dialect: Dialect) -> (String, Option<Vec<Arg>>) {
+        self.render(self.by.to_sql(), dialect)
+    }
+    /// Orders a compound (UNION etc.) result, which can only refer to output column names.
+    pub fn to_compound_sql(&self, dialect: Dialect) -> (String, Option<Vec<Arg>>) {
+        self.render(self.by.to_compound_sql(), dialect)
+    }
+    fn render(&self, by: (String, Option<Vec<Arg>>), dialect: Dialect) -> (String, Option<Vec<Arg>>) {
+        let (by_sql, by_args) = by;
+        let by_args = by_args.unwrap_or_default();
+        let dir = self.dir.to_sql().0;
+        match (&self.nulls, dialect) {
+            (None, _) => (format!("{by_sql} {dir}"), Some(by_args)),
+            (Some(Nulls::First), Dialect::Postgres) => {
+                (format!("{by_sql} {dir} NULLS FIRST"), Some(by_args))
+            }
+            (Some(Nulls::Last), Dialect::Postgres) => {
+                (format!("{by_sql} {dir} NULLS LAST"), Some(by_args))
+            }
+            (Some(nulls), _) => {
+                let null_dir = match nulls {
+                    Nulls::First => "DESC",
+                    Nulls::Last => "ASC",
+                };
+                let mut args = by_args.clone();
+                args.extend(by_args);
+                (
+                    format!("{by_sql} IS NULL {null_dir}, {by_sql} {dir}"),
+                    Some(args),
+                )
+            }
+        }
     }
 }
+
 impl ToSQL for Order {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
-        let (col_sql, col_args) = self.by.to_sql();
-        (format!("{} {}", col_sql, self.dir.to_sql().0), col_args)
+        self.to_dialect_sql(Dialect::MySQL)
+    }
+}
+
+impl Subqueries for Order {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.by.subqueries(f);
     }
 }
@@ -1,24 +1,145 @@
 use crate::args::*;
 use crate::expressions::*;
-use crate::statements::{Dir, Order};
+use crate::functions::*;
+use crate::hints::*;
+use crate::json::*;
+use crate::mysql::MYSQLBuilder;
+use crate::statements::{Dir, Order, SelectItem};
 use crate::traits::*;
+use std::ops::{Add, Div, Mul, Sub};
 
+#[derive(Clone, Debug, PartialEq)]
+enum TableSource {
+    Name(String),
+    Json(Box<JsonTable>),
+    Values(Values),
+    Derived(Box<MYSQLBuilder>, bool),
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Table {
-    name: String,
+    source: TableSource,
+    alias: Option<String>,
+    hints: Vec<IndexHint>,
+}
+
+impl From<&'static str> for Table {
+    fn from(table_name: &'static str) -> Self {
+        tb(table_name)
+    }
 }
 
 pub fn tb(table_name: &'static str) -> Table {
     Table::new(table_name.to_string())
 }
 
+/// An inline table of bound rows: `(VALUES ROW(?, ?), ...) AS alias (col, ...)`.
+pub fn values_table<T: ToExpTar>(
+    keys: Vec<&'static str>,
+    values: Vec<Vec<T>>,
+    alias: &'static str,
+) -> Table {
+    Table::values(
+        Values::new(
+            keys.iter().map(|k| k.to_string()).collect(),
+            values
+                .into_iter()
+                .map(|row| row.into_iter().map(|val| val.to_exp_tar()).collect())
+                .collect(),
+        ),
+        alias,
+    )
+}
+
 impl Table {
     pub fn new(name: String) -> Self {
-        return Table { name: name };
+        Table {
+            source: TableSource::Name(name),
+            alias: None,
+            hints: vec![],
+        }
+    }
+    pub fn json(json_table: JsonTable, alias: &'static str) -> Self {
+        Table {
+            source: TableSource::Json(Box::new(json_table)),
+            alias: Some(alias.to_string()),
+            hints: vec![],
+        }
+    }
+    pub fn values(values: Values, alias: &'static str) -> Self {
+        Table {
+            source: TableSource::Values(values),
+            alias: Some(alias.to_string()),
+            hints: vec![],
+        }
+    }
+    /// A subquery used as a table: `(SELECT ...) AS alias`.
+    pub fn derived(query: MYSQLBuilder, alias: &'static str) -> Self {
+        Table {
+            source: TableSource::Derived(Box::new(query), false),
+            alias: Some(alias.to_string()),
+            hints: vec![],
+        }
+    }
+    /// A derived table that may reference columns of the tables before it: `LATERAL (SELECT ...) AS alias`.
+    pub fn lateral(query: MYSQLBuilder, alias: &'static str) -> Self {
+        Table {
+            source: TableSource::Derived(Box::new(query), true),
+            alias: Some(alias.to_string()),
+            hints: vec![],
+        }
+    }
+    fn name(&self) -> &str {
+        match &self.source {
+            TableSource::Name(name) => name,
+            TableSource::Json(_) => "JSON_TABLE",
+            TableSource::Values(_) => "VALUES",
+            TableSource::Derived(..) => "",
+        }
+    }
+    pub fn as_alias(mut self, val: &'static str) -> Self {
+        self.alias = Some(val.to_string());
+        self
+    }
+    pub fn use_index(self, indexes: Vec<&'static str>) -> Self {
+        self.index_hint(IndexHint::new(IndexHintKind::Use, indexes))
+    }
+    pub fn force_index(self, indexes: Vec<&'static str>) -> Self {
+        self.index_hint(IndexHint::new(IndexHintKind::Force, indexes))
+    }
+    pub fn ignore_index(self, indexes: Vec<&'static str>) -> Self {
+        self.index_hint(IndexHint::new(IndexHintKind::Ignore, indexes))
+    }
+    pub fn index_hint(mut self, hint: IndexHint) -> Self {
+        self.hints.push(hint);
+        self
+    }
+    /// `table.*`, for use in a select list.
+    pub fn all(&self) -> SelectItem {
+        SelectItem::TableStar(self.ref_sql())
+    }
+    pub fn has_hints(&self) -> bool {
+        !self.hints.is_empty()
+    }
+    pub fn is_values(&self) -> bool {
+        matches!(self.source, TableSource::Values(_))
+    }
+    pub fn is_lateral(&self) -> bool {
+        matches!(self.source, TableSource::Derived(_, true))
+    }
+    /// The name other clauses use to refer to this table: its alias if it has one.
+    pub fn ref_sql(&self) -> String {
+        match &self.alias {
+            Some(alias) => format!("`{alias}`"),
+            None => format!("`{}`", self.name()),
+        }
     }
     fn col(&self, name: String) -> Col {
         Col {
-            table_name: self.name.clone(),
-            column: name,
+            base: ColBase::Field {
+                table_name: self.alias.clone().unwrap_or(self.name().to_string()),
+                column: name,
+            },
             alias: None,
             wrapper: None,
         }
@@ -27,11 +148,41 @@ impl Table {
 
 impl ToSQL for Table {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
-        (format!("`{}`", self.name), None)
+        let (mut sql, args) = match &self.source {
+            TableSource::Name(name) => (format!("`{name}`"), None),
+            TableSource::Json(json_table) => json_table.to_sql(),
+            TableSource::Values(values) => values.to_sql(),
+            TableSource::Derived(query, lateral) => {
+                let (query_sql, query_args) = query.nested_sql();
+                let lateral = if *lateral { "LATERAL " } else { "" };
+                (format!("{lateral}({query_sql})"), Some(query_args))
+            }
+        };
+        if let Some(alias) = &self.alias {
+            sql.push_str(format!(" AS `{alias}`").as_str());
+        }
+        if let TableSource::Values(values) = &self.source {
+            sql.push_str(format!(" {}", values.keys_sql()).as_str());
+        }
+        for hint in &self.hints {
+            sql.push_str(format!(" {}", hint.to_sql().0).as_str());
+        }
+        (sql, args)
     }
 }
 
-#[derive(Clone)]
+impl Subqueries for Table {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match &mut self.source {
+            TableSource::Json(json_table) => json_table.subqueries(f),
+            TableSource::Values(values) => values.subqueries(f),
+            TableSource::Derived(query, _) => f(query),
+            TableSource::Name(_) => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 enum Wrapper {
     Distinct(Option<Box<Wrapper>>),
     Count(Option<Box<Wrapper>>),
@@ -39,32 +190,82 @@ enum Wrapper {
     Max(Option<Box<Wrapper>>),
     Min(Option<Box<Wrapper>>),
     Avg(Option<Box<Wrapper>>),
-    Concat(Option<Box<Wrapper>>),
-    Instr((Option<Box<Wrapper>>), String),
-    Coalesce(Option<Box<Wrapper>>),
+    BitAnd(Option<Box<Wrapper>>),
+    BitOr(Option<Box<Wrapper>>),
+    BitXor(Option<Box<Wrapper>>),
+    JsonArrayAgg(Option<Box<Wrapper>>),
+    Concat(Option<Box<Wrapper>>, Vec<Order>, Option<String>),
+    Instr(Option<Box<Wrapper>>, Box<FuncArg>),
+    Coalesce(Option<Box<Wrapper>>, Vec<FuncArg>),
 }
 
 impl Wrapper {
-    fn to_sql(&self, parent_sql: String) -> String {
+    fn to_sql(&self, parent: (String, Vec<Arg>)) -> (String, Vec<Arg>) {
+        let (inner_sql, mut args) = match self.inner() {
+            Some(sub) => sub.to_sql(parent),
+            None => parent,
+        };
+        let sql = match self {
+            Wrapper::Count(_) => format!("COUNT({inner_sql})"),
+            Wrapper::Sum(_) => format!("SUM({inner_sql})"),
+            Wrapper::Max(_) => format!("MAX({inner_sql})"),
+            Wrapper::Min(_) => format!("MIN({inner_sql})"),
+            Wrapper::Avg(_) => format!("AVG({inner_sql})"),
+            Wrapper::BitAnd(_) => format!("BIT_AND({inner_sql})"),
+            Wrapper::BitOr(_) => format!("BIT_OR({inner_sql})"),
+            Wrapper::BitXor(_) => format!("BIT_XOR({inner_sql})"),
+            Wrapper::JsonArrayAgg(_) => format!("JSON_ARRAYAGG({inner_sql})"),
+            Wrapper::Concat(_, order, separator) => {
+                let mut sql = format!("GROUP_CONCAT({inner_sql}");
+                if !order.is_empty() {
+                    let mut order_sql = vec![];
+                    order.iter().for_each(|o| {
+                        let (o_sql, o_args) = o.to_sql();
+                        order_sql.push(o_sql);
+                        args.extend(o_args.unwrap_or_default());
+                    });
+                    sql.push_str(format!(" ORDER BY {}", order_sql.join(", ")).as_str());
+                }
+                // SEPARATOR only takes a string literal, so it can't be bound
+                if let Some(sep) = separator {
+                    sql.push_str(format!(" SEPARATOR {}", quote_str(sep)).as_str());
+                }
+                sql.push(')');
+                sql
+            }
+            Wrapper::Instr(_, search) => {
+                let (search_sql, search_args) = search.to_sql();
+                args.extend(search_args.unwrap_or_default());
+                format!("INSTR({inner_sql}, {search_sql})")
+            }
+            Wrapper::Coalesce(_, fallbacks) => {
+                let mut sql = vec![inner_sql];
+                fallbacks.iter().for_each(|f| {
+                    let (f_sql, f_args) = f.to_sql();
+                    sql.push(f_sql);
+                    args.extend(f_args.unwrap_or_default());
+                });
+                format!("COALESCE({})", sql.join(", "))
+            }
+            Wrapper::Distinct(_) => format!("DISTINCT {inner_sql}"),
+        };
+        (sql, args)
+    }
+    fn inner(&self) -> Option<&Wrapper> {
         match self {
-            Wrapper::Count(Some(sub)) => format!("COUNT({})", sub.to_sql(parent_sql)),
-            Wrapper::Count(_) => format!("COUNT({parent_sql})"),
-            Wrapper::Sum(Some(sub)) => format!("SUM({})", sub.to_sql(parent_sql)),
-            Wrapper::Sum(_) => format!("SUM({parent_sql})"),
-            Wrapper::Max(Some(sub)) => format!("MAX({})", sub.to_sql(parent_sql)),
-            Wrapper::Max(_) => format!("MAX({parent_sql})"),
-            Wrapper::Min(Some(sub)) => format!("MIN({})", sub.to_sql(parent_sql)),
-            Wrapper::Min(_) => format!("MIN({parent_sql})"),
-            Wrapper::Avg(Some(sub)) => format!("AVG({})", sub.to_sql(parent_sql)),
-            Wrapper::Avg(_) => format!("AVG({parent_sql})"),
-            Wrapper::Concat(Some(sub)) => format!("GROUP_CONCAT({})", sub.to_sql(parent_sql)),
-            Wrapper::Concat(_) => format!("GROUP_CONCAT({parent_sql})"),
-            Wrapper::Instr(Some(sub), txt) => format!("INSTR({}, {txt})", sub.to_sql(parent_sql)),
-            Wrapper::Instr(_, txt) => format!("INSTR({parent_sql}, {txt})"),
-            Wrapper::Coalesce(Some(sub)) => format!("COALESCE({})", sub.to_sql(parent_sql)),
-            Wrapper::Coalesce(_) => format!("COALESC({parent_sql})"),
-            Wrapper::Distinct(Some(sub)) => format!("DISTINCT {}", sub.to_sql(parent_sql)),
-            Wrapper::Distinct(_) => format!("DISTINCT {parent_sql}"),
+            Wrapper::Count(sub)
+            | Wrapper::Sum(sub)
+            | Wrapper::Max(sub)
+            | Wrapper::Min(sub)
+            | Wrapper::Avg(sub)
+            | Wrapper::BitAnd(sub)
+            | Wrapper::BitOr(sub)
+            | Wrapper::BitXor(sub)
+            | Wrapper::JsonArrayAgg(sub)
+            | Wrapper::Concat(sub, _, _)
+            | Wrapper::Instr(sub, _)
+            | Wrapper::Coalesce(sub, _)
+            | Wrapper::Distinct(sub) => sub.as_deref(),
         }
     }
     fn wrap(self, outer: Wrapper) -> Self {
@@ -74,18 +275,280 @@ impl Wrapper {
             Wrapper::Max(_) => Wrapper::Max(Some(Box::new(self))),
             Wrapper::Min(_) => Wrapper::Min(Some(Box::new(self))),
             Wrapper::Avg(_) => Wrapper::Avg(Some(Box::new(self))),
-            Wrapper::Concat(_) => Wrapper::Concat(Some(Box::new(self))),
-            Wrapper::Instr(_, txt) => Wrapper::Instr(Some(Box::new(self)), txt),
-            Wrapper::Coalesce(_) => Wrapper::Coalesce(Some(Box::new(self))),
+            Wrapper::BitAnd(_) => Wrapper::BitAnd(Some(Box::new(self))),
+            Wrapper::BitOr(_) => Wrapper::BitOr(Some(Box::new(self))),
+            Wrapper::BitXor(_) => Wrapper::BitXor(Some(Box::new(self))),
+            Wrapper::JsonArrayAgg(_) => Wrapper::JsonArrayAgg(Some(Box::new(self))),
+            Wrapper::Concat(_, order, separator) => {
+                Wrapper::Concat(Some(Box::new(self)), order, separator)
+            }
+            Wrapper::Instr(_, search) => Wrapper::Instr(Some(Box::new(self)), search),
+            Wrapper::Coalesce(_, fallbacks) => Wrapper::Coalesce(Some(Box::new(self)), fallbacks),
             Wrapper::Distinct(_) => Wrapper::Distinct(Some(Box::new(self))),
         }
     }
 }
 
-#[derive(Clone)]
+impl Subqueries for Wrapper {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match self {
+            Wrapper::Concat(sub, order, _) => {
+                sub.subqueries(f);
+                order.subqueries(f);
+            }
+            Wrapper::Instr(sub, val) => {
+                sub.subqueries(f);
+                val.subqueries(f);
+            }
+            Wrapper::Coalesce(sub, vals) => {
+                sub.subqueries(f);
+                vals.subqueries(f);
+            }
+            Wrapper::Distinct(sub)
+            | Wrapper::Count(sub)
+            | Wrapper::Sum(sub)
+            | Wrapper::Max(sub)
+            | Wrapper::Min(sub)
+            | Wrapper::Avg(sub)
+            | Wrapper::BitAnd(sub)
+            | Wrapper::BitOr(sub)
+            | Wrapper::BitXor(sub)
+            | Wrapper::JsonArrayAgg(sub) => sub.subqueries(f),
+        }
+    }
+}
+
+/// Quotes a string literal for the few places MySQL won't take a bound `?`.
+pub fn quote_str(val: &str) -> String {
+    format!("'{}'", val.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FuncArg {
+    A(Arg),
+    C(Col),
+    E(Box<Exp>),
+    Interval(Box<FuncArg>, Unit),
+    Star,
+}
+
+impl<T: ToArg> From<T> for FuncArg {
+    fn from(val: T) -> Self {
+        FuncArg::A(val.to_arg())
+    }
+}
+impl From<Col> for FuncArg {
+    fn from(col: Col) -> Self {
+        FuncArg::C(col.without_alias())
+    }
+}
+impl From<Exp> for FuncArg {
+    fn from(exp: Exp) -> Self {
+        FuncArg::E(Box::new(exp))
+    }
+}
+
+impl ToSQL for FuncArg {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        match self {
+            FuncArg::A(arg) => (String::from("?"), Some(vec![arg.clone()])),
+            FuncArg::C(col) => col.to_sql(),
+            FuncArg::E(exp) => {
+                let (sql, args) = exp.to_sql();
+                (format!("({sql})"), args)
+            }
+            FuncArg::Interval(amount, unit) => {
+                let (sql, args) = amount.to_sql();
+                (format!("INTERVAL {sql} {}", unit.to_sql().0), args)
+            }
+            FuncArg::Star => (String::from("*"), None),
+        }
+    }
+}
+
+impl Subqueries for FuncArg {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match self {
+            FuncArg::C(col) => col.subqueries(f),
+            FuncArg::E(exp) => exp.subqueries(f),
+            FuncArg::Interval(val, _) => val.subqueries(f),
+            FuncArg::A(_) | FuncArg::Star => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Func {
+    name: String,
+    args: Vec<FuncArg>,
+    distinct: bool,
+}
+
+impl Func {
+    pub fn new(name: &'static str, args: Vec<FuncArg>) -> Self {
+        Func {
+            name: name.to_string(),
+            args,
+            distinct: false,
+        }
+    }
+    /// Renders as `NAME(DISTINCT args...)`.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+}
+
+impl From<MatchAgainst> for Col {
+    fn from(m: MatchAgainst) -> Self {
+        Col {
+            base: ColBase::Match(Box::new(m)),
+            alias: None,
+            wrapper: None,
+        }
+    }
+}
+
+impl From<Cast> for Col {
+    fn from(c: Cast) -> Self {
+        Col {
+            base: ColBase::Cast(Box::new(c)),
+            alias: None,
+            wrapper: None,
+        }
+    }
+}
+
+impl From<Func> for Col {
+    fn from(f: Func) -> Self {
+        Col {
+            base: ColBase::Func(f),
+            alias: None,
+            wrapper: None,
+        }
+    }
+}
+
+impl ToSQL for Func {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mut arg_sql = vec![];
+        let mut args = vec![];
+        self.args.iter().for_each(|a| {
+            let (sql, a) = a.to_sql();
+            arg_sql.push(sql);
+            if let Some(v) = a {
+                args.extend(v);
+            }
+        });
+        let distinct = if self.distinct { "DISTINCT " } else { "" };
+        (
+            format!("{}({distinct}{})", self.name, arg_sql.join(", ")),
+            Some(args),
+        )
+    }
+}
+
+impl Subqueries for Func {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.args.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    whens: Vec<(Exp, FuncArg)>,
+    otherwise: Option<Box<FuncArg>>,
+}
+
+pub fn case() -> Case {
+    Case {
+        whens: vec![],
+        otherwise: None,
+    }
+}
+
+impl Case {
+    pub fn when<T: Into<FuncArg>>(mut self, cond: Exp, then: T) -> Self {
+        self.whens.push((cond, then.into()));
+        self
+    }
+    pub fn otherwise<T: Into<FuncArg>>(mut self, val: T) -> Self {
+        self.otherwise = Some(Box::new(val.into()));
+        self
+    }
+    pub fn end(self) -> Col {
+        Col {
+            base: ColBase::Case(self),
+            alias: None,
+            wrapper: None,
+        }
+    }
+}
+
+impl ToSQL for Case {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        let mut sql = String::from("CASE");
+        let mut args = vec![];
+        self.whens.iter().for_each(|(cond, then)| {
+            let (cond_sql, cond_args) = cond.to_sql();
+            let (then_sql, then_args) = then.to_sql();
+            sql.push_str(format!(" WHEN {cond_sql} THEN {then_sql}").as_str());
+            args.extend(cond_args.unwrap_or_default());
+            args.extend(then_args.unwrap_or_default());
+        });
+        if let Some(otherwise) = &self.otherwise {
+            let (else_sql, else_args) = otherwise.to_sql();
+            sql.push_str(format!(" ELSE {else_sql}").as_str());
+            args.extend(else_args.unwrap_or_default());
+        }
+        sql.push_str(" END");
+        (sql, Some(args))
+    }
+}
+
+impl Subqueries for Case {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        for (cond, then) in &mut self.whens {
+            cond.subqueries(f);
+            then.subqueries(f);
+        }
+        self.otherwise.subqueries(f);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl ToSQL for ArithOp {
+    fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
+        match self {
+            ArithOp::Add => (String::from("+"), None),
+            ArithOp::Sub => (String::from("-"), None),
+            ArithOp::Mul => (String::from("*"), None),
+            ArithOp::Div => (String::from("/"), None),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ColBase {
+    Field { table_name: String, column: String },
+    Func(Func),
+    Case(Case),
+    Arith(Box<FuncArg>, ArithOp, Box<FuncArg>),
+    Match(Box<MatchAgainst>),
+    Cast(Box<Cast>),
+    Collate(Box<FuncArg>, String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Col {
-    table_name: String,
-    column: String,
+    base: ColBase,
     alias: Option<String>,
     wrapper: Option<Wrapper>,
 }
@@ -94,22 +557,66 @@ pub fn cl(table: &'static str, col: &'static str) -> Col {
     Col::new(table, col)
 }
 
+/// Escapes `\`, `%` and `_` so `text` matches literally inside a `LIKE ... ESCAPE '\\'` pattern.
+pub fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub fn func(name: &'static str, args: Vec<FuncArg>) -> Col {
+    Func::new(name, args).into()
+}
+
 impl Col {
     pub fn new(table: &'static str, col: &'static str) -> Self {
         Col {
-            table_name: table.to_string(),
-            column: col.to_string(),
+            base: ColBase::Field {
+                table_name: table.to_string(),
+                column: col.to_string(),
+            },
             alias: None,
             wrapper: None,
         }
     }
     pub fn name(&self) -> &str {
-        &self.column
+        match &self.base {
+            ColBase::Field { column, .. } => column,
+            ColBase::Func(f) => &f.name,
+            ColBase::Case(_) => "CASE",
+            ColBase::Arith(..) => "",
+            ColBase::Match(_) => "MATCH",
+            ColBase::Cast(_) => "CAST",
+            ColBase::Collate(..) => "",
+        }
+    }
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
     }
     pub fn as_alias(mut self, val: &'static str) -> Self {
         self.alias = Some(val.to_string());
         self
     }
+    pub(crate) fn set_alias(&mut self, val: String) {
+        self.alias = Some(val);
+    }
+    pub fn without_alias(mut self) -> Self {
+        self.alias = None;
+        self
+    }
+    /// The name of this column in a result set: its alias, or the bare column name.
+    pub fn ref_sql(&self) -> (String, Option<Vec<Arg>>) {
+        match (&self.alias, &self.base, &self.wrapper) {
+            (Some(alias), _, _) => (format!("`{alias}`"), None),
+            (None, ColBase::Field { column, .. }, None) => (format!("`{column}`"), None),
+            _ => self.to_sql(),
+        }
+    }
     pub fn eq<T: ToExpTar>(self, exp: T) -> Exp {
         self.make_exp(exp.to_exp_tar(), Op::Eq)
     }
@@ -122,8 +629,44 @@ impl Col {
     pub fn gt<T: ToExpTar>(&self, exp: T) -> Exp {
         self.make_exp(exp.to_exp_tar(), Op::Gt)
     }
-    pub fn like(&self, search: &'static str) -> Exp {
-        self.make_exp(search.to_exp_tar(), Op::Like)
+    pub fn like<S: Into<String>>(&self, pattern: S) -> Exp {
+        self.make_exp(pattern.into().to_exp_tar(), Op::Like)
+    }
+    /// Case-insensitive `like`: `LOWER(col) LIKE LOWER(?)`.
+    pub fn ilike<S: Into<String>>(&self, pattern: S) -> Exp {
+        self.make_lower_exp(pattern.into(), Op::Like)
+    }
+    /// `col LIKE ? ESCAPE '\\'` matching `text` anywhere; `%` and `_` in `text` match literally.
+    pub fn contains<S: Into<String>>(&self, text: S) -> Exp {
+        let pattern = format!("%{}%", escape_like(&text.into()));
+        self.make_exp(pattern.to_exp_tar(), Op::LikeEscaped)
+    }
+    pub fn starts_with<S: Into<String>>(&self, text: S) -> Exp {
+        let pattern = format!("{}%", escape_like(&text.into()));
+        self.make_exp(pattern.to_exp_tar(), Op::LikeEscaped)
+    }
+    pub fn ends_with<S: Into<String>>(&self, text: S) -> Exp {
+        let pattern = format!("%{}", escape_like(&text.into()));
+        self.make_exp(pattern.to_exp_tar(), Op::LikeEscaped)
+    }
+    pub fn icontains<S: Into<String>>(&self, text: S) -> Exp {
+        let pattern = format!("%{}%", escape_like(&text.into()));
+        self.make_lower_exp(pattern, Op::LikeEscaped)
+    }
+    pub fn istarts_with<S: Into<String>>(&self, text: S) -> Exp {
+        let pattern = format!("{}%", escape_like(&text.into()));
+        self.make_lower_exp(pattern, Op::LikeEscaped)
+    }
+    pub fn iends_with<S: Into<String>>(&self, text: S) -> Exp {
+        let pattern = format!("%{}", escape_like(&text.into()));
+        self.make_lower_exp(pattern, Op::LikeEscaped)
+    }
+    fn make_lower_exp(&self, pattern: String, op: Op) -> Exp {
+        Exp::Exp(ExpU::new(
+            op,
+            ExpTar::C(lower(self.clone())),
+            ExpTar::C(lower(pattern)),
+        ))
     }
     pub fn r#in<T: ToExpTar>(&self, exp: T) -> Exp {
         self.make_exp(exp.to_exp_tar(), Op::In)
@@ -135,7 +678,7 @@ impl Col {
         self.make_exp(ExpTar::Null, Op::IsNot)
     }
     fn make_exp(&self, comp: ExpTar, op: Op) -> Exp {
-        Exp::Exp(ExpU::new(op, ExpTar::C(self.clone()), comp))
+        Exp::Exp(ExpU::new(op, self.clone().to_exp_tar(), comp))
     }
     pub fn asc(&self) -> Order {
         Order::new(self.clone(), Dir::Asc)
@@ -162,13 +705,45 @@ impl Col {
         self.do_wrapper(Wrapper::Avg(None))
     }
     pub fn concat(self) -> Self {
-        self.do_wrapper(Wrapper::Concat(None))
+        self.do_wrapper(Wrapper::Concat(None, vec![], None))
+    }
+    pub fn group_concat(self) -> Self {
+        self.concat()
+    }
+    /// Adds an `ORDER BY` inside the outermost `GROUP_CONCAT`; no-op on other columns.
+    pub fn order_by(mut self, order: Order) -> Self {
+        if let Some(Wrapper::Concat(_, orders, _)) = &mut self.wrapper {
+            orders.push(order);
+        }
+        self
+    }
+    /// Sets the `SEPARATOR` of the outermost `GROUP_CONCAT`; no-op on other columns.
+    pub fn separator(mut self, separator: &str) -> Self {
+        if let Some(Wrapper::Concat(_, _, sep)) = &mut self.wrapper {
+            *sep = Some(separator.to_string());
+        }
+        self
+    }
+    pub fn bit_and(self) -> Self {
+        self.do_wrapper(Wrapper::BitAnd(None))
+    }
+    pub fn bit_or(self) -> Self {
+        self.do_wrapper(Wrapper::BitOr(None))
     }
-    pub fn instr(self, search: &'static str) -> Self {
-        self.do_wrapper(Wrapper::Instr(None, search.to_string()))
+    pub fn bit_xor(self) -> Self {
+        self.do_wrapper(Wrapper::BitXor(None))
+    }
+    pub fn json_arrayagg(self) -> Self {
+        self.do_wrapper(Wrapper::JsonArrayAgg(None))
+    }
+    pub fn instr<T: Into<FuncArg>>(self, search: T) -> Self {
+        self.do_wrapper(Wrapper::Instr(None, Box::new(search.into())))
     }
     pub fn coalesce(self) -> Self {
-        self.do_wrapper(Wrapper::Coalesce(None))
+        self.do_wrapper(Wrapper::Coalesce(None, vec![]))
+    }
+    pub fn coalesce_with(self, fallbacks: Vec<FuncArg>) -> Self {
+        self.do_wrapper(Wrapper::Coalesce(None, fallbacks))
     }
     fn do_wrapper(mut self, wrapper: Wrapper) -> Self {
         match self.wrapper {
@@ -181,14 +756,112 @@ impl Col {
 
 impl ToSQL for Col {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>) {
-        let sql = format!("`{}`.`{}`", self.table_name, self.column);
-        let mut sql = match &self.wrapper {
-            Some(wrapper) => wrapper.to_sql(sql),
-            None => sql,
+        let base = match &self.base {
+            ColBase::Field { table_name, column } => (format!("`{table_name}`.`{column}`"), vec![]),
+            ColBase::Func(f) => {
+                let (sql, args) = f.to_sql();
+                (sql, args.unwrap_or_default())
+            }
+            ColBase::Case(c) => {
+                let (sql, args) = c.to_sql();
+                (sql, args.unwrap_or_default())
+            }
+            ColBase::Arith(left, op, right) => {
+                let (left_sql, left_args) = left.to_sql();
+                let (right_sql, right_args) = right.to_sql();
+                let mut args = left_args.unwrap_or_default();
+                args.extend(right_args.unwrap_or_default());
+                (format!("({left_sql} {} {right_sql})", op.to_sql().0), args)
+            }
+            ColBase::Match(m) => {
+                let (sql, args) = m.to_sql();
+                (sql, args.unwrap_or_default())
+            }
+            ColBase::Cast(c) => {
+                let (sql, args) = c.to_sql();
+                (sql, args.unwrap_or_default())
+            }
+            ColBase::Collate(val, collation) => {
+                let (sql, args) = val.to_sql();
+                (format!("{sql} COLLATE {collation}"), args.unwrap_or_default())
+            }
+        };
+        let (mut sql, args) = match &self.wrapper {
+            Some(wrapper) => wrapper.to_sql(base),
+            None => base,
         };
         if let Some(val) = &self.alias {
             sql.push_str(format!(" AS {val}").as_str())
         }
-        (sql, None)
+        (sql, Some(args))
+    }
+}
+
+impl Subqueries for Col {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        match &mut self.base {
+            ColBase::Field { .. } => {}
+            ColBase::Func(func) => func.subqueries(f),
+            ColBase::Case(case) => case.subqueries(f),
+            ColBase::Arith(left, _, right) => {
+                left.subqueries(f);
+                right.subqueries(f);
+            }
+            ColBase::Match(m) => m.subqueries(f),
+            ColBase::Cast(c) => c.subqueries(f),
+            ColBase::Collate(val, _) => val.subqueries(f),
+        }
+        self.wrapper.subqueries(f);
+    }
+}
+
+impl Col {
+    /// `CAST(col AS type)`.
+    pub fn cast(self, to: SqlType) -> Col {
+        cast(self, to)
+    }
+    /// `CONVERT(col USING charset)`.
+    pub fn convert_using(self, charset: &'static str) -> Col {
+        convert_using(self, charset)
+    }
+    /// `col COLLATE name`, for comparisons and ordering under a specific collation.
+    pub fn collate(self, collation: &'static str) -> Col {
+        Col {
+            base: ColBase::Collate(Box::new(self.into()), collation.to_string()),
+            alias: None,
+            wrapper: None,
+        }
+    }
+    fn arith<T: Into<FuncArg>>(self, op: ArithOp, rhs: T) -> Col {
+        Col {
+            base: ColBase::Arith(Box::new(self.into()), op, Box::new(rhs.into())),
+            alias: None,
+            wrapper: None,
+        }
+    }
+}
+
+impl<T: Into<FuncArg>> Add<T> for Col {
+    type Output = Col;
+    fn add(self, rhs: T) -> Col {
+        self.arith(ArithOp::Add, rhs)
+    }
+}
+impl<T: Into<FuncArg>> Sub<T> for Col {
+    type Output = Col;
+    fn sub(self, rhs: T) -> Col {
+        self.arith(ArithOp::Sub, rhs)
+    }
+}
+impl<T: Into<FuncArg>> Mul<T> for Col {
+    type Output = Col;
+    fn mul(self, rhs: T) -> Col {
+        self.arith(ArithOp::Mul, rhs)
+    }
+}
+impl<T: Into<FuncArg>> Div<T> for Col {
+    type Output = Col;
+    fn div(self, rhs: T) -> Col {
+        self.arith(ArithOp::Div, rhs)
     }
 }
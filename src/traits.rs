@@ -1,29 +1,119 @@
 use crate::args::*;
+use crate::dialect::*;
+use crate::error::*;
 use crate::expressions::*;
+use crate::hints::*;
+use crate::mysql::MYSQLBuilder;
 use crate::statements::*;
 use crate::table::*;
 
 pub trait QueryBuilder {
     fn query() -> Self;
-    fn to_sql(&self) -> (String, Vec<Arg>);
+    fn dialect(self, dialect: Dialect) -> Self;
+    fn build(&self) -> Result<(String, Vec<Arg>), BuildError>;
+    /// Like `build`, but panics if the query can't be rendered for its dialect.
+    fn to_sql(&self) -> (String, Vec<Arg>) {
+        match self.build() {
+            Ok(built) => built,
+            Err(err) => panic!("{err}"),
+        }
+    }
+    /// Runs `f` on the builder only if `cond` holds, keeping the chain unbroken.
+    fn when<F: FnOnce(Self) -> Self>(self, cond: bool, f: F) -> Self
+    where
+        Self: Sized,
+    {
+        if cond {
+            f(self)
+        } else {
+            self
+        }
+    }
+    /// Runs `f` with the value only if there is one, e.g. for optional query parameters.
+    fn when_some<T, F: FnOnce(Self, T) -> Self>(self, val: Option<T>, f: F) -> Self
+    where
+        Self: Sized,
+    {
+        match val {
+            Some(val) => f(self, val),
+            None => self,
+        }
+    }
+    fn apply<S: Scope<Self>>(self, scope: S) -> Self
+    where
+        Self: Sized,
+    {
+        scope.apply(self)
+    }
+}
+
+/// A reusable piece of a query, such as "only active rows", applied with `QueryBuilder::apply`.
+pub trait Scope<B> {
+    fn apply(&self, query: B) -> B;
+}
+
+impl<B, F: Fn(B) -> B> Scope<B> for F {
+    fn apply(&self, query: B) -> B {
+        self(query)
+    }
 }
 
 pub trait FetchQBuilder {
-    fn from(self, table_name: &'static str) -> Self;
-    fn select(self, cols: Vec<Col>) -> Self;
+    fn from<T: Into<Table>>(self, table: T) -> Self;
+    fn select<T: Into<SelectItem>>(self, cols: Vec<T>) -> Self;
+    fn replace_select<T: Into<SelectItem>>(self, cols: Vec<T>) -> Self;
+    fn pluck(self, col: Col) -> Self;
     fn distinct(self) -> Self;
+    fn hint(self, hint: OptimizerHint) -> Self;
     fn join(self, table: Table, on: On) -> Self;
     fn left_join(self, table: Table, on: Exp) -> Self;
     fn right_join(self, table: Table, on: Exp) -> Self;
+    fn cross_join(self, table: Table) -> Self;
+    fn join_lateral(self, query: Self, alias: &'static str, on: Option<Exp>) -> Self;
+    fn left_join_lateral(self, query: Self, alias: &'static str, on: Option<Exp>) -> Self;
     fn union(self, query: Self) -> Self;
-    fn order(self, by: Col, dir: Dir) -> Self;
+    fn union_all(self, query: Self) -> Self;
+    fn intersect(self, query: Self) -> Self;
+    fn except(self, query: Self) -> Self;
+    fn compound_order<T: Into<OrderBy>>(self, by: T, dir: Dir) -> Self;
+    fn compound_limit(self, by: i32) -> Self;
+    fn order<T: Into<OrderBy>>(self, by: T, dir: Dir) -> Self;
+    fn order_by(self, order: Order) -> Self;
+    fn order_by_field<T: ToArg>(self, col: Col, values: Vec<T>) -> Self;
     fn group_by(self, by: Col) -> Self;
-    fn having(self, exp: ExpU) -> Self;
+    fn with_rollup(self) -> Self;
+    fn rollup(self, cols: Vec<Col>) -> Self;
+    fn cube(self, cols: Vec<Col>) -> Self;
+    fn grouping_sets(self, sets: Vec<Vec<Col>>) -> Self;
+    fn having(self, exp: Exp) -> Self;
     fn limit(self, by: i32) -> Self;
+    fn clear_order(self) -> Self;
+    fn clear_limit(self) -> Self;
+    fn for_update(self) -> Self;
+    fn for_share(self) -> Self;
+    fn lock_in_share_mode(self) -> Self;
+    fn nowait(self) -> Self;
+    fn skip_locked(self) -> Self;
+    fn of(self, tables: Vec<Table>) -> Self;
 }
 
 pub trait WhereQBuilder {
     fn r#where(self, exp: Exp) -> Self;
+    fn clear_where(self) -> Self;
+    /// Adds the condition if there is one, otherwise leaves the query as is.
+    fn where_opt(self, exp: Option<Exp>) -> Self
+    where
+        Self: Sized,
+    {
+        match exp {
+            Some(exp) => self.r#where(exp),
+            None => self,
+        }
+    }
+}
+
+pub trait ReturningQBuilder {
+    fn returning(self, cols: Vec<Col>) -> Self;
 }
 
 pub trait UpdateQBuilder {
@@ -33,13 +123,40 @@ pub trait UpdateQBuilder {
 
 pub trait InsertQBuilder {
     fn insert(self, table: Table) -> Self;
-    fn rows(self, keys: Vec<&'static str>, values: Vec<Vec<Arg>>) -> Self;
+    fn rows<T: ToExpTar>(self, keys: Vec<&'static str>, values: Vec<Vec<T>>) -> Self;
 }
 
 pub trait DeleteQBuilder {
     fn delete(self, table: Table) -> Self;
+    fn targets(self, tables: Vec<Table>) -> Self;
 }
 
 pub trait ToSQL {
     fn to_sql(&self) -> (String, Option<Vec<Arg>>);
 }
+
+/// Walks the builders nested in a node (subqueries, derived tables, `EXISTS`), so `build()`
+/// can hand them its dialect and check them before rendering the outer query.
+pub trait Subqueries {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder));
+}
+
+impl<T: Subqueries> Subqueries for Vec<T> {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        self.iter_mut().for_each(|node| node.subqueries(f));
+    }
+}
+
+impl<T: Subqueries> Subqueries for Option<T> {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        if let Some(node) = self {
+            node.subqueries(f);
+        }
+    }
+}
+
+impl<T: Subqueries> Subqueries for Box<T> {
+    fn subqueries(&mut self, f: &mut dyn FnMut(&mut MYSQLBuilder)) {
+        (**self).subqueries(f);
+    }
+}